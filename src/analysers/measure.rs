@@ -0,0 +1,131 @@
+use std::f64::consts::PI;
+
+use tracing::{
+    debug,
+    instrument,
+};
+
+use crate::processors::Biquad;
+
+/// Gating block length and hop for integrated loudness (ITU-R BS.1770-4).
+const BLOCK_LENGTH: f64 = 0.4;
+const BLOCK_HOP: f64 = 0.1;
+const ABSOLUTE_GATE: f64 = -70.0;
+const RELATIVE_GATE: f64 = -10.0;
+
+/// Largest absolute sample value.
+pub fn peak(samples: &[f64]) -> f64 {
+    samples.iter().fold(0.0, |acc: f64, s| acc.max(s.abs()))
+}
+
+/// Root mean square of the samples.
+pub fn rms(samples: &[f64]) -> f64 {
+    if samples.is_empty() {
+        return 0.0;
+    }
+    (samples.iter().map(|s| s * s).sum::<f64>() / samples.len() as f64).sqrt()
+}
+
+/// The two BS.1770 K-weighting stages (a high shelf modelling the head and
+/// a highpass), derived for any sample rate rather than the tabulated
+/// 48 kHz coefficients.
+fn k_weighting(sample_rate: f64) -> [Biquad; 2] {
+    let shelf = {
+        let (f0, gain, q) = (1681.974450955533, 3.999843853973347, 0.7071752369554196);
+        let k = (PI * f0 / sample_rate).tan();
+        let vh = 10f64.powf(gain / 20.0);
+        let vb = vh.powf(0.4996667741545416);
+        Biquad::from_coefficients(
+            [vh + vb * k / q + k * k, 2.0 * (k * k - vh), vh - vb * k / q + k * k],
+            [1.0 + k / q + k * k, 2.0 * (k * k - 1.0), 1.0 - k / q + k * k],
+        )
+    };
+    // As in BS.1770 and libebur128, only the feedback side is normalised:
+    // the numerator stays [1, -2, 1], which keeps the 0 dB reference
+    let highpass = {
+        let (f0, q) = (38.13547087602444, 0.5003270373238773);
+        let k = (PI * f0 / sample_rate).tan();
+        let a0 = 1.0 + k / q + k * k;
+        Biquad::from_coefficients(
+            [1.0, -2.0, 1.0],
+            [1.0, 2.0 * (k * k - 1.0) / a0, (1.0 - k / q + k * k) / a0],
+        )
+    };
+    [shelf, highpass]
+}
+
+fn block_loudness(mean_square: f64) -> f64 {
+    -0.691 + 10.0 * mean_square.max(1e-20).log10()
+}
+
+/// Gated integrated loudness in LUFS, or `None` when everything falls below
+/// the absolute gate. Buffers shorter than one gating block are measured as
+/// a single block.
+#[instrument(skip(samples), fields(num_samples = %samples.len(), sample_rate = %sample_rate))]
+pub fn integrated_loudness(samples: &[f64], sample_rate: f64) -> Option<f64> {
+    let mut filters = k_weighting(sample_rate);
+    let weighted: Vec<f64> = samples
+        .iter()
+        .map(|&s| filters.iter_mut().fold(s, |x, filter| filter.process_sample(x)))
+        .collect();
+
+    let block = ((BLOCK_LENGTH * sample_rate).round() as usize).clamp(1, weighted.len().max(1));
+    let hop = ((BLOCK_HOP * sample_rate).round() as usize).max(1);
+    let powers: Vec<f64> = (0..)
+        .map(|i| i * hop)
+        .take_while(|&start| start + block <= weighted.len())
+        .map(|start| {
+            weighted[start..start + block].iter().map(|s| s * s).sum::<f64>() / block as f64
+        })
+        .collect();
+
+    let above_absolute: Vec<f64> =
+        powers.iter().copied().filter(|&p| block_loudness(p) > ABSOLUTE_GATE).collect();
+    if above_absolute.is_empty() {
+        debug!("All {} blocks fall below the absolute gate", powers.len());
+        return None;
+    }
+    let relative_gate =
+        block_loudness(above_absolute.iter().sum::<f64>() / above_absolute.len() as f64)
+            + RELATIVE_GATE;
+    let gated: Vec<f64> =
+        above_absolute.into_iter().filter(|&p| block_loudness(p) > relative_gate).collect();
+
+    let loudness = block_loudness(gated.iter().sum::<f64>() / gated.len() as f64);
+    debug!(
+        "Integrated loudness {:.2} LUFS over {} of {} blocks",
+        loudness,
+        gated.len(),
+        powers.len()
+    );
+    Some(loudness)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Sine at `amplitude` lasting `duration` seconds.
+    fn sine(freq: f64, amplitude: f64, duration: f64, sample_rate: f64) -> Vec<f64> {
+        (0..(duration * sample_rate) as usize)
+            .map(|n| amplitude * (2.0 * PI * freq * n as f64 / sample_rate).sin())
+            .collect()
+    }
+
+    #[test]
+    fn reference_sine_reads_minus_23_lufs() {
+        // BS.1770 calibration: a 997 Hz sine at -20 dBFS reads -23 LUFS
+        for sample_rate in [44100.0, 48000.0] {
+            let loudness = integrated_loudness(&sine(997.0, 0.1, 5.0, sample_rate), sample_rate);
+            let loudness = loudness.unwrap();
+            assert!((loudness + 23.0).abs() < 0.1, "{} LUFS at {} Hz", loudness, sample_rate);
+        }
+    }
+
+    #[test]
+    fn silence_is_gated_out() {
+        assert_eq!(integrated_loudness(&[0.0; 48000], 48000.0), None);
+        let quiet = sine(997.0, 1e-5, 1.0, 48000.0);
+        assert_eq!(integrated_loudness(&quiet, 48000.0), None);
+    }
+}
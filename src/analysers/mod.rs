@@ -1,3 +1,4 @@
+pub mod measure;
 mod peak;
 
 pub use peak::PeakAnalyser;
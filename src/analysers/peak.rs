@@ -6,6 +6,7 @@ use tracing::{
     instrument,
 };
 
+use super::measure;
 use crate::traits::{
     Analyser,
     Component,
@@ -37,13 +38,19 @@ impl PeakAnalyser {
     }
 }
 
+impl Default for PeakAnalyser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Analyser for PeakAnalyser {
     type Output = f64;
 
     #[instrument(skip(self, samples), fields(num_samples = %samples.len()))]
     fn analyze(&mut self, samples: &[f64]) -> Self::Output {
         debug!("Analyzing {} samples for peak", samples.len());
-        let peak = samples.iter().cloned().fold(0.0, f64::max);
+        let peak = measure::peak(samples);
         self.last_peak = Some(peak);
         info!("Peak value found: {:.6}", peak);
         peak
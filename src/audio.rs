@@ -4,8 +4,30 @@ use tracing::{
     debug,
     info,
     instrument,
+    warn,
 };
 
+/// Largest 24-bit sample value.
+const PCM_MAX: f64 = 8388607.0;
+
+/// Converts to 24-bit PCM, clamping anything beyond full scale instead of
+/// wrapping around.
+fn to_pcm(sample: f64) -> i32 {
+    (sample.clamp(-1.0, 1.0) * PCM_MAX) as i32
+}
+
+/// Warns when samples exceed full scale and will be clipped on output.
+fn warn_if_clipping(samples: &[f64]) {
+    let clipped = samples.iter().filter(|s| s.abs() > 1.0).count();
+    if clipped > 0 {
+        let peak = samples.iter().fold(0.0f64, |acc, s| acc.max(s.abs()));
+        warn!(
+            "{} samples exceed full scale (peak {:.2}) and will be clipped; add a limiter",
+            clipped, peak
+        );
+    }
+}
+
 #[instrument(skip(samples), fields(filename = %filename, num_samples = %samples.len(), sample_rate = %sample_rate))]
 pub fn write_wav(filename: &str, samples: &[f64], sample_rate: f64) -> Result<(), hound::Error> {
     debug!("Creating WAV file with {} samples at {}Hz", samples.len(), sample_rate);
@@ -17,12 +39,12 @@ pub fn write_wav(filename: &str, samples: &[f64], sample_rate: f64) -> Result<()
         sample_format: hound::SampleFormat::Int,
     };
 
+    warn_if_clipping(samples);
     let mut writer = hound::WavWriter::create(filename, spec)?;
     debug!("WAV writer created for {}", filename);
 
     for (i, &sample) in samples.iter().enumerate() {
-        let pcm = (sample * 8388607.0) as i32;
-        writer.write_sample(pcm)?;
+        writer.write_sample(to_pcm(sample))?;
 
         // Log progress for very long samples
         if samples.len() > 100000 && i % 50000 == 0 {
@@ -44,14 +66,81 @@ pub fn write_wav_to_bytes(samples: &[f64], sample_rate: f64) -> Result<Vec<u8>,
         sample_format: hound::SampleFormat::Int,
     };
 
+    warn_if_clipping(samples);
     let mut cursor = std::io::Cursor::new(Vec::new());
     let mut writer = hound::WavWriter::new(&mut cursor, spec)?;
 
     for &sample in samples {
-        let pcm = (sample * 8388607.0) as i32;
-        writer.write_sample(pcm)?;
+        writer.write_sample(to_pcm(sample))?;
     }
 
     writer.finalize()?;
     Ok(cursor.into_inner())
 }
+
+pub struct WavData {
+    pub channels: Vec<Vec<f64>>,
+    pub sample_rate: f64,
+}
+
+#[instrument(fields(filename = %filename))]
+pub fn read_wav(filename: &str) -> Result<WavData, hound::Error> {
+    let mut reader = hound::WavReader::open(filename)?;
+    let spec = reader.spec();
+    debug!(
+        "Reading WAV file: {} channels, {} Hz, {} bits, {:?}",
+        spec.channels, spec.sample_rate, spec.bits_per_sample, spec.sample_format
+    );
+
+    let interleaved: Vec<f64> = match spec.sample_format {
+        hound::SampleFormat::Int => {
+            let scale = (1i64 << (spec.bits_per_sample - 1)) as f64;
+            reader
+                .samples::<i32>()
+                .map(|s| s.map(|s| s as f64 / scale))
+                .collect::<Result<_, _>>()?
+        }
+        hound::SampleFormat::Float => {
+            reader.samples::<f32>().map(|s| s.map(f64::from)).collect::<Result<_, _>>()?
+        }
+    };
+
+    let num_channels = spec.channels.max(1) as usize;
+    let mut channels = vec![Vec::with_capacity(interleaved.len() / num_channels); num_channels];
+    for frame in interleaved.chunks_exact(num_channels) {
+        for (channel, &sample) in channels.iter_mut().zip(frame) {
+            channel.push(sample);
+        }
+    }
+
+    info!("WAV file read successfully: {} ({} frames)", filename, channels[0].len());
+    Ok(WavData { channels, sample_rate: spec.sample_rate as f64 })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pcm_conversion_clamps_instead_of_wrapping() {
+        assert_eq!(to_pcm(0.0), 0);
+        assert_eq!(to_pcm(1.0), PCM_MAX as i32);
+        assert_eq!(to_pcm(-1.0), -(PCM_MAX as i32));
+        assert_eq!(to_pcm(4.0), PCM_MAX as i32);
+        assert_eq!(to_pcm(-4.0), -(PCM_MAX as i32));
+        assert_eq!(to_pcm(f64::INFINITY), PCM_MAX as i32);
+    }
+
+    #[test]
+    fn out_of_range_samples_survive_round_trip_clipped() {
+        let path = std::env::temp_dir().join(format!("noise-clip-{}.wav", std::process::id()));
+        let path = path.to_str().unwrap();
+        write_wav(path, &[0.5, 1.5, -1.5], 44100.0).unwrap();
+        let wav = read_wav(path).unwrap();
+        std::fs::remove_file(path).unwrap();
+        let samples = &wav.channels[0];
+        assert!((samples[0] - 0.5).abs() < 1e-6);
+        assert!((samples[1] - 1.0).abs() < 1e-6);
+        assert!((samples[2] + 1.0).abs() < 1e-6);
+    }
+}
@@ -61,10 +61,11 @@ fn run_pipeline(cli: &Cli) -> Result<()> {
 
     info!("Running pipeline");
     let samples = pipeline.run(cli.duration, cli.sample_rate)?;
-    info!("Generated {} samples", samples.len());
+    let output_rate = pipeline.output_sample_rate(cli.sample_rate);
+    info!("Generated {} samples at {} Hz", samples.len(), output_rate);
 
     let _span = span!(Level::INFO, "write_output", file = %cli.output).entered();
-    write_wav(&cli.output, &samples, cli.sample_rate)?;
+    write_wav(&cli.output, &samples, output_rate)?;
     info!("Saved {} samples to {}", samples.len(), cli.output);
 
     Ok(())
@@ -101,12 +101,13 @@ async fn generate_audio(
         )
     })?;
 
-    info!("Generated {} samples", samples.len());
+    let output_rate = pipeline.output_sample_rate(req.sample_rate);
+    info!("Generated {} samples at {} Hz", samples.len(), output_rate);
 
     Ok(Json(GenerateResponse {
         samples: samples.len(),
-        duration: req.duration,
-        sample_rate: req.sample_rate,
+        duration: samples.len() as f64 / output_rate,
+        sample_rate: output_rate,
         pipeline: req.pipeline,
     }))
 }
@@ -162,7 +163,8 @@ async fn generate_audio_wav(
         )
     })?;
 
-    let wav_bytes = write_wav_to_bytes(&samples, req.sample_rate).map_err(|e| {
+    let output_rate = pipeline.output_sample_rate(req.sample_rate);
+    let wav_bytes = write_wav_to_bytes(&samples, output_rate).map_err(|e| {
         (
             StatusCode::INTERNAL_SERVER_ERROR,
             Json(ErrorResponse {
@@ -11,6 +11,7 @@ use tracing::{
 };
 
 use crate::factory::create_component;
+use crate::parser::parse_components;
 use crate::traits::{
     Component,
     Source,
@@ -42,7 +43,7 @@ impl Parallel {
         }
 
         debug!("Parsing parallel component specs from: {}", inner);
-        let comp_specs: Vec<&str> = inner.split(',').map(|s| s.trim()).collect();
+        let comp_specs = parse_components(inner);
         debug!("Found {} component specs in parallel", comp_specs.len());
 
         let mut components = Vec::new();
@@ -6,10 +6,48 @@ use tracing::instrument;
 
 use crate::analysers::PeakAnalyser;
 use crate::composite::Parallel;
-use crate::processors::VolumeProcessor;
+use crate::processors::{
+    ChorusProcessor,
+    CompressorProcessor,
+    CrushProcessor,
+    DelayProcessor,
+    DistortProcessor,
+    EnvelopeProcessor,
+    FadeProcessor,
+    FilterProcessor,
+    FlangerProcessor,
+    GateProcessor,
+    LimiterProcessor,
+    NormalizeProcessor,
+    PhaserProcessor,
+    ResampleProcessor,
+    ReverbProcessor,
+    TremoloProcessor,
+    VibratoProcessor,
+    VolumeProcessor,
+};
 use crate::sources::{
+    AmSource,
+    BurstSource,
+    DcSource,
+    FileSource,
+    FmSource,
+    GranularSource,
+    ImpulseSource,
+    MidiSource,
+    NoiseSource,
+    Note,
+    PluckSource,
+    PulseWaveSource,
+    SawWaveSource,
+    SeqSource,
+    SilenceSource,
     SineWaveSource,
     SquareWaveSource,
+    StepSource,
+    SweepSource,
+    TriangleWaveSource,
+    WavetableSource,
 };
 use crate::traits::Component;
 
@@ -19,10 +57,84 @@ pub fn create_component(spec: &str) -> Result<Box<dyn Component>> {
     let comp: Box<dyn Component> = match parts[0] {
         "sine" => Box::new(SineWaveSource::from_spec(spec)?),
         "square" => Box::new(SquareWaveSource::from_spec(spec)?),
+        "saw" => Box::new(SawWaveSource::from_spec(spec)?),
+        "triangle" => Box::new(TriangleWaveSource::from_spec(spec)?),
+        "pulse" => Box::new(PulseWaveSource::from_spec(spec)?),
+        "sweep" => Box::new(SweepSource::from_spec(spec)?),
+        "wavetable" => Box::new(WavetableSource::from_spec(spec)?),
+        "fm" => Box::new(FmSource::from_spec(spec)?),
+        "am" => Box::new(AmSource::from_spec(spec)?),
+        "file" => Box::new(FileSource::from_spec(spec)?),
+        "seq" => Box::new(SeqSource::from_spec(spec)?),
+        "midi" => Box::new(MidiSource::from_spec(spec)?),
+        "pluck" => Box::new(PluckSource::from_spec(spec)?),
+        "granular" => Box::new(GranularSource::from_spec(spec)?),
+        "impulse" => Box::new(ImpulseSource::from_spec(spec)?),
+        "step" => Box::new(StepSource::from_spec(spec)?),
+        "dc" => Box::new(DcSource::from_spec(spec)?),
+        "silence" => Box::new(SilenceSource::from_spec(spec)?),
+        "burst" => Box::new(BurstSource::from_spec(spec)?),
+        "white" | "pink" | "brown" | "blue" | "violet" => Box::new(NoiseSource::from_spec(spec)?),
         "parallel" => Box::new(Parallel::from_spec(spec)?),
         "volume" => Box::new(VolumeProcessor::from_spec(spec)?),
+        "envelope" => Box::new(EnvelopeProcessor::from_spec(spec)?),
+        "delay" => Box::new(DelayProcessor::from_spec(spec)?),
+        "reverb" => Box::new(ReverbProcessor::from_spec(spec)?),
+        "compressor" => Box::new(CompressorProcessor::from_spec(spec)?),
+        "limiter" => Box::new(LimiterProcessor::from_spec(spec)?),
+        "gate" => Box::new(GateProcessor::from_spec(spec)?),
+        "distort" => Box::new(DistortProcessor::from_spec(spec)?),
+        "crush" => Box::new(CrushProcessor::from_spec(spec)?),
+        "fade" => Box::new(FadeProcessor::from_spec(spec)?),
+        "normalize" => Box::new(NormalizeProcessor::from_spec(spec)?),
+        "tremolo" => Box::new(TremoloProcessor::from_spec(spec)?),
+        "vibrato" => Box::new(VibratoProcessor::from_spec(spec)?),
+        "chorus" => Box::new(ChorusProcessor::from_spec(spec)?),
+        "flanger" => Box::new(FlangerProcessor::from_spec(spec)?),
+        "phaser" => Box::new(PhaserProcessor::from_spec(spec)?),
+        "resample" => Box::new(ResampleProcessor::from_spec(spec)?),
+        "lowpass" | "highpass" | "bandpass" | "notch" | "lowshelf" | "highshelf" | "peak_eq" => {
+            Box::new(FilterProcessor::from_spec(spec)?)
+        }
         "peak" => Box::new(PeakAnalyser::from_spec(spec)?),
+        // Panning needs a stereo pipeline; point at the nearest mono effect
+        "autopan" | "auto_pan" | "pan" => bail!(
+            "{} is not supported: the pipeline is mono, so there is nothing to pan; \
+             tremolo gives the same level movement",
+            parts[0]
+        ),
+        // A bare note is what is left of a seq note list that was split at its commas
+        _ if Note::parse(spec).is_ok() => bail!(
+            "Unknown component type: {} looks like a note; seq note lists must be bracketed: \
+             seq:notes=[C4/0.25,E4/0.25]",
+            spec
+        ),
         _ => bail!("Unknown component type: {}", parts[0]),
     };
     Ok(comp)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::parse_components;
+
+    #[test]
+    fn stray_notes_suggest_brackets() {
+        let specs = parse_components("seq:notes=[C4/0.25],E4/0.25,volume:level=0.5");
+        assert!(create_component(&specs[0]).is_ok());
+        let error = create_component(&specs[1]).err().unwrap().to_string();
+        assert!(error.contains("seq:notes=[C4/0.25,E4/0.25]"), "{}", error);
+        assert!(create_component(&specs[2]).is_ok());
+
+        let error = create_component("wobble:rate=2").err().unwrap().to_string();
+        assert_eq!(error, "Unknown component type: wobble");
+    }
+
+    #[test]
+    fn autopan_explains_mono_pipeline() {
+        let error = create_component("autopan:rate=2").err().unwrap().to_string();
+        assert!(error.contains("pipeline is mono"), "{}", error);
+        assert!(error.contains("tremolo"), "{}", error);
+    }
+}
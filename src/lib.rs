@@ -14,6 +14,10 @@ pub mod factory;
 
 pub mod audio;
 
+pub mod midi;
+
 pub mod pipeline;
 
 pub mod parser;
+
+pub mod random;
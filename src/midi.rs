@@ -0,0 +1,347 @@
+use std::collections::{
+    BTreeMap,
+    VecDeque,
+};
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+/// Default tempo of a Standard MIDI File until a tempo event says otherwise
+/// (120 bpm).
+const DEFAULT_TEMPO: u32 = 500_000;
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct MidiNote {
+    pub channel: u8,
+    pub key: u8,
+    pub velocity: u8,
+    /// Start time in seconds.
+    pub start: f64,
+    /// Length in seconds.
+    pub duration: f64,
+}
+
+#[derive(Debug, Clone, Copy)]
+enum Timing {
+    TicksPerQuarter(u16),
+    /// Seconds per tick for SMPTE time division.
+    Smpte(f64),
+}
+
+#[derive(Debug)]
+struct RawNote {
+    channel: u8,
+    key: u8,
+    velocity: u8,
+    start_tick: u64,
+    end_tick: u64,
+}
+
+struct Reader<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn new(data: &'a [u8]) -> Self {
+        Self { data, pos: 0 }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.pos >= self.data.len()
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8]> {
+        let end = self.pos.checked_add(len).filter(|&end| end <= self.data.len());
+        let end = end.ok_or_else(|| eyre!("Unexpected end of MIDI data"))?;
+        let bytes = &self.data[self.pos..end];
+        self.pos = end;
+        Ok(bytes)
+    }
+
+    fn u8(&mut self) -> Result<u8> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn peek(&self) -> Result<u8> {
+        self.data.get(self.pos).copied().ok_or_else(|| eyre!("Unexpected end of MIDI data"))
+    }
+
+    fn u32(&mut self) -> Result<u32> {
+        let bytes = self.take(4)?;
+        Ok(u32::from_be_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Variable-length quantity: 7 bits per byte, high bit set on all but
+    /// the last byte.
+    fn vlq(&mut self) -> Result<u32> {
+        let mut value: u32 = 0;
+        for _ in 0..4 {
+            let byte = self.u8()?;
+            value = (value << 7) | u32::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                return Ok(value);
+            }
+        }
+        bail!("Variable-length quantity longer than 4 bytes")
+    }
+}
+
+/// Parses a Standard MIDI File (format 0 or 1) into notes with absolute
+/// times, following the file's tempo map.
+#[instrument(skip(data), fields(len = %data.len()))]
+pub fn parse_midi(data: &[u8]) -> Result<Vec<MidiNote>> {
+    let mut reader = Reader::new(data);
+    if reader.take(4)? != b"MThd" {
+        bail!("Not a Standard MIDI File (missing MThd header)");
+    }
+    let header_len = reader.u32()? as usize;
+    let header = reader.take(header_len)?;
+    if header_len < 6 {
+        bail!("MIDI header too short");
+    }
+    let format = u16::from_be_bytes([header[0], header[1]]);
+    let num_tracks = u16::from_be_bytes([header[2], header[3]]);
+    let division = u16::from_be_bytes([header[4], header[5]]);
+    if format == 2 {
+        // Format 2 tracks are independent sequential patterns, each with its
+        // own tempo, and cannot be merged onto one timeline
+        bail!("MIDI format 2 (sequential patterns) is not supported; use format 0 or 1");
+    }
+    if format > 1 {
+        bail!("Unsupported MIDI format: {}", format);
+    }
+
+    let timing = if division & 0x8000 == 0 {
+        Timing::TicksPerQuarter(division.max(1))
+    } else {
+        let fps = match -((division >> 8) as u8 as i8) {
+            29 => 29.97,
+            fps => f64::from(fps),
+        };
+        let ticks_per_frame = f64::from((division & 0xFF).max(1));
+        Timing::Smpte(1.0 / (fps * ticks_per_frame))
+    };
+    debug!("MIDI format {} with {} tracks, timing {:?}", format, num_tracks, timing);
+
+    let mut tempo_changes: Vec<(u64, u32)> = Vec::new();
+    let mut raw_notes = Vec::new();
+
+    while !reader.is_empty() {
+        let chunk_type = reader.take(4)?;
+        let chunk_len = reader.u32()? as usize;
+        let chunk = reader.take(chunk_len)?;
+        if chunk_type != b"MTrk" {
+            debug!("Skipping unknown chunk {:?}", String::from_utf8_lossy(chunk_type));
+            continue;
+        }
+        parse_track(chunk, &mut tempo_changes, &mut raw_notes)?;
+    }
+
+    tempo_changes.sort_by_key(|&(tick, _)| tick);
+    let to_seconds = |tick: u64| ticks_to_seconds(tick, timing, &tempo_changes);
+
+    let mut notes: Vec<MidiNote> = raw_notes
+        .iter()
+        .map(|raw| {
+            let start = to_seconds(raw.start_tick);
+            MidiNote {
+                channel: raw.channel,
+                key: raw.key,
+                velocity: raw.velocity,
+                start,
+                duration: to_seconds(raw.end_tick) - start,
+            }
+        })
+        .collect();
+    notes.sort_by(|a, b| a.start.total_cmp(&b.start));
+
+    info!("Parsed {} notes from MIDI data", notes.len());
+    Ok(notes)
+}
+
+fn parse_track(
+    chunk: &[u8],
+    tempo_changes: &mut Vec<(u64, u32)>,
+    raw_notes: &mut Vec<RawNote>,
+) -> Result<()> {
+    let mut reader = Reader::new(chunk);
+    let mut tick: u64 = 0;
+    let mut running_status: Option<u8> = None;
+    let mut open: BTreeMap<(u8, u8), VecDeque<(u64, u8)>> = BTreeMap::new();
+
+    while !reader.is_empty() {
+        tick += u64::from(reader.vlq()?);
+
+        let status = if reader.peek()? & 0x80 != 0 {
+            reader.u8()?
+        } else {
+            running_status.ok_or_else(|| eyre!("Running status without a previous status"))?
+        };
+
+        match status {
+            // Meta and sysex events cancel running status, so a data byte
+            // after one is malformed rather than a continued channel message
+            0xFF => {
+                running_status = None;
+                let meta_type = reader.u8()?;
+                let len = reader.vlq()? as usize;
+                let payload = reader.take(len)?;
+                match meta_type {
+                    0x51 if len == 3 => {
+                        let tempo = u32::from_be_bytes([0, payload[0], payload[1], payload[2]]);
+                        tempo_changes.push((tick, tempo));
+                    }
+                    0x2F => break,
+                    _ => {}
+                }
+            }
+            0xF0 | 0xF7 => {
+                running_status = None;
+                let len = reader.vlq()? as usize;
+                reader.take(len)?;
+            }
+            0x80..=0xEF => {
+                running_status = Some(status);
+                let channel = status & 0x0F;
+                match status & 0xF0 {
+                    0x80 | 0x90 => {
+                        let key = reader.u8()?;
+                        let velocity = reader.u8()?;
+                        let notes = open.entry((channel, key)).or_default();
+                        if status & 0xF0 == 0x90 && velocity > 0 {
+                            notes.push_back((tick, velocity));
+                        } else if let Some((start_tick, velocity)) = notes.pop_front() {
+                            raw_notes.push(RawNote {
+                                channel,
+                                key,
+                                velocity,
+                                start_tick,
+                                end_tick: tick,
+                            });
+                        }
+                    }
+                    0xC0 | 0xD0 => {
+                        reader.u8()?;
+                    }
+                    _ => {
+                        reader.take(2)?;
+                    }
+                }
+            }
+            _ => bail!("Unsupported MIDI status byte: {:#04x}", status),
+        }
+    }
+
+    // Notes never switched off last until the end of their track
+    for ((channel, key), notes) in open {
+        for (start_tick, velocity) in notes {
+            raw_notes.push(RawNote { channel, key, velocity, start_tick, end_tick: tick });
+        }
+    }
+    Ok(())
+}
+
+fn ticks_to_seconds(tick: u64, timing: Timing, tempo_changes: &[(u64, u32)]) -> f64 {
+    let ticks_per_quarter = match timing {
+        Timing::TicksPerQuarter(ticks) => f64::from(ticks),
+        Timing::Smpte(seconds_per_tick) => return tick as f64 * seconds_per_tick,
+    };
+
+    let mut seconds = 0.0;
+    let mut last_tick = 0;
+    let mut tempo = DEFAULT_TEMPO;
+    for &(change_tick, new_tempo) in tempo_changes {
+        if change_tick >= tick {
+            break;
+        }
+        seconds += (change_tick - last_tick) as f64 * f64::from(tempo) / 1e6 / ticks_per_quarter;
+        last_tick = change_tick;
+        tempo = new_tempo;
+    }
+    seconds + (tick - last_tick) as f64 * f64::from(tempo) / 1e6 / ticks_per_quarter
+}
+
+#[instrument(fields(filename = %filename))]
+pub fn read_midi(filename: &str) -> Result<Vec<MidiNote>> {
+    let data = std::fs::read(filename).map_err(|e| eyre!("Failed to read {}: {}", filename, e))?;
+    parse_midi(&data)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn smf(format: u16, tracks: &[&[u8]]) -> Vec<u8> {
+        let mut data = b"MThd".to_vec();
+        data.extend(6u32.to_be_bytes());
+        data.extend(format.to_be_bytes());
+        data.extend((tracks.len() as u16).to_be_bytes());
+        data.extend(480u16.to_be_bytes());
+        for track in tracks {
+            data.extend(b"MTrk");
+            data.extend((track.len() as u32).to_be_bytes());
+            data.extend(*track);
+        }
+        data
+    }
+
+    /// 60 bpm; C4 for one beat (switched off by running status with velocity
+    /// 0), then E4 for two beats.
+    const TRACK: &[u8] = &[
+        0x00, 0xFF, 0x51, 0x03, 0x0F, 0x42, 0x40, //
+        0x00, 0x90, 60, 100, //
+        0x83, 0x60, 60, 0, //
+        0x00, 0x90, 64, 80, //
+        0x87, 0x40, 0x80, 64, 0, //
+        0x00, 0xFF, 0x2F, 0x00,
+    ];
+
+    #[test]
+    fn parses_format_0() {
+        let notes = parse_midi(&smf(0, &[TRACK])).unwrap();
+        assert_eq!(
+            notes,
+            [
+                MidiNote { channel: 0, key: 60, velocity: 100, start: 0.0, duration: 1.0 },
+                MidiNote { channel: 0, key: 64, velocity: 80, start: 1.0, duration: 2.0 },
+            ]
+        );
+    }
+
+    #[test]
+    fn meta_event_cancels_running_status() {
+        // A note-on, a text meta event, then a bare data byte pair
+        let track: &[u8] = &[
+            0x00, 0x90, 60, 100, //
+            0x00, 0xFF, 0x01, 0x01, b'x', //
+            0x00, 64, 100, //
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        let error = parse_midi(&smf(0, &[track])).unwrap_err();
+        assert!(error.to_string().contains("Running status"), "{}", error);
+
+        // The same bytes are valid with the status repeated
+        let track: &[u8] = &[
+            0x00, 0x90, 60, 100, //
+            0x00, 0xFF, 0x01, 0x01, b'x', //
+            0x00, 0x90, 64, 100, //
+            0x00, 0xFF, 0x2F, 0x00,
+        ];
+        assert_eq!(parse_midi(&smf(0, &[track])).unwrap().len(), 2);
+    }
+
+    #[test]
+    fn rejects_format_2() {
+        let error = parse_midi(&smf(2, &[TRACK, TRACK])).unwrap_err();
+        assert!(error.to_string().contains("format 2"), "{}", error);
+    }
+}
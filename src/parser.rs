@@ -1,4 +1,9 @@
-use tracing::{debug, instrument};
+use color_eyre::Result;
+use color_eyre::eyre::eyre;
+use tracing::{
+    debug,
+    instrument,
+};
 
 #[instrument(level = "debug")]
 pub fn parse_components(pipeline: &str) -> Vec<String> {
@@ -36,3 +41,97 @@ pub fn parse_components(pipeline: &str) -> Vec<String> {
 
     components
 }
+
+/// Parses a time value in seconds, accepting an optional `s` or `ms` suffix
+/// (`0.05`, `0.05s` and `50ms` are equivalent).
+pub fn parse_duration(value: &str) -> Result<f64> {
+    let value = value.trim();
+    let (number, scale) = if let Some(ms) = value.strip_suffix("ms") {
+        (ms, 1e-3)
+    } else if let Some(s) = value.strip_suffix('s') {
+        (s, 1.0)
+    } else {
+        (value, 1.0)
+    };
+    let seconds: f64 = number.trim().parse().map_err(|_| eyre!("Invalid duration: {}", value))?;
+    Ok(seconds * scale)
+}
+
+/// Parses a level in decibels, accepting an optional `dB` suffix (`-6` and
+/// `-6dB` are equivalent).
+pub fn parse_decibels(value: &str) -> Result<f64> {
+    let value = value.trim();
+    let number = value.strip_suffix("dB").or_else(|| value.strip_suffix("db")).unwrap_or(value);
+    number.trim().parse().map_err(|_| eyre!("Invalid decibel value: {}", value))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn splits_plain_chain() {
+        assert_eq!(
+            parse_components("sine:freq=440, lowpass:freq=1000,volume:level=0.5"),
+            ["sine:freq=440", "lowpass:freq=1000", "volume:level=0.5"]
+        );
+        assert_eq!(parse_components("sine,,volume,"), ["sine", "volume"]);
+    }
+
+    #[test]
+    fn keeps_bracketed_note_list_together() {
+        assert_eq!(
+            parse_components("seq:notes=[C4/0.25,E4/0.25,G4/0.5],fade:in=0.01:out=0.1"),
+            ["seq:notes=[C4/0.25,E4/0.25,G4/0.5]", "fade:in=0.01:out=0.1"]
+        );
+        assert_eq!(
+            parse_components("seq:wave=saw:notes=[c4/0.25,e4/0.25,r/0.1,g4/0.5],volume"),
+            ["seq:wave=saw:notes=[c4/0.25,e4/0.25,r/0.1,g4/0.5]", "volume"]
+        );
+    }
+
+    #[test]
+    fn unbracketed_note_list_is_rejected() {
+        // Without brackets the list is cut at its commas, and seq reports the
+        // missing brackets for its own piece
+        let components = parse_components("seq:wave=sine:notes=C4/0.25,E4/0.25,G4/0.5");
+        assert_eq!(components, ["seq:wave=sine:notes=C4/0.25", "E4/0.25", "G4/0.5"]);
+        let error = crate::factory::create_component(&components[0]).err().unwrap().to_string();
+        assert!(error.contains("notes=[C4/0.25,E4/0.25]"), "{}", error);
+    }
+
+    #[test]
+    fn keeps_nested_parallel_together() {
+        let spec = "parallel:[sine:freq=220,parallel:[seq:notes=[a3/0.5,c4/0.5],square]],volume";
+        let components = parse_components(spec);
+        assert_eq!(
+            components,
+            ["parallel:[sine:freq=220,parallel:[seq:notes=[a3/0.5,c4/0.5],square]]", "volume"]
+        );
+
+        let inner = &components[0]["parallel:[".len()..components[0].len() - 1];
+        assert_eq!(
+            parse_components(inner),
+            ["sine:freq=220", "parallel:[seq:notes=[a3/0.5,c4/0.5],square]"]
+        );
+    }
+
+    #[test]
+    fn durations_accept_seconds_and_milliseconds() {
+        assert_eq!(parse_duration("0.05").unwrap(), 0.05);
+        assert_eq!(parse_duration("0.05s").unwrap(), 0.05);
+        assert!((parse_duration("50ms").unwrap() - 0.05).abs() < 1e-15);
+        assert_eq!(parse_duration(" 2 s").unwrap(), 2.0);
+        assert!(parse_duration("fast").is_err());
+        assert!(parse_duration("5min").is_err());
+    }
+
+    #[test]
+    fn decibels_accept_optional_suffix() {
+        assert_eq!(parse_decibels("-6").unwrap(), -6.0);
+        assert_eq!(parse_decibels("-6dB").unwrap(), -6.0);
+        assert_eq!(parse_decibels("3 db").unwrap(), 3.0);
+        assert!(parse_decibels("loud").is_err());
+        assert!(parse_decibels("-6dBFS").is_err());
+    }
+}
@@ -37,10 +37,24 @@ impl Pipeline {
         Ok(())
     }
 
+    /// Components in pipeline order, for inspecting their state after a run.
+    pub fn components(&self) -> &[Box<dyn Component>] {
+        &self.components
+    }
+
+    /// Rate of the pipeline's output when it runs at `sample_rate`, after any
+    /// sample-rate conversion along the way.
+    pub fn output_sample_rate(&self, sample_rate: f64) -> f64 {
+        self.components
+            .iter()
+            .fold(sample_rate, |rate, component| component.output_sample_rate(rate))
+    }
+
     #[instrument(skip(self), fields(duration = %duration, sample_rate = %sample_rate, num_components = %self.components.len()))]
     pub fn run(&mut self, duration: f64, sample_rate: f64) -> Result<Vec<f64>> {
         info!("Running pipeline with {} components", self.components.len());
         let mut buffer = Vec::new();
+        let mut rate = sample_rate;
 
         for (i, component) in self.components.iter_mut().enumerate() {
             let _span =
@@ -48,13 +62,30 @@ impl Pipeline {
                     .entered();
             debug!("Processing component {} (buffer has {} samples)", i, buffer.len());
 
-            component.process(&mut buffer, duration, sample_rate)?;
+            // Processors may grow the buffer (e.g. a delay tail), so later
+            // components see the current length rather than the requested one
+            let current_duration =
+                if buffer.is_empty() { duration } else { buffer.len() as f64 / rate };
+            component.process(&mut buffer, current_duration, rate)?;
 
+            let next_rate = component.output_sample_rate(rate);
+            if next_rate != rate {
+                debug!(
+                    "Component {} changed the sample rate from {} Hz to {} Hz",
+                    i, rate, next_rate
+                );
+                rate = next_rate;
+            }
             debug!("Component {} processed, buffer now has {} samples", i, buffer.len());
         }
 
-        info!("Pipeline completed with {} samples", buffer.len());
+        info!("Pipeline completed with {} samples at {} Hz", buffer.len(), rate);
         Ok(buffer)
     }
+}
 
+impl Default for Pipeline {
+    fn default() -> Self {
+        Self::new()
+    }
 }
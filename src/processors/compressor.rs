@@ -0,0 +1,254 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::parser::{
+    parse_decibels,
+    parse_duration,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+/// Per-sample coefficient of a one-pole smoother that covers ~63% of a step
+/// in `time` seconds; zero time means no smoothing.
+pub(crate) fn smoothing_coefficient(time: f64, sample_rate: f64) -> f64 {
+    if time <= 0.0 { 0.0 } else { (-1.0 / (time * sample_rate)).exp() }
+}
+
+/// Level in dB of a sample, floored so that silence stays finite.
+pub(crate) fn level_db(sample: f64) -> f64 {
+    20.0 * sample.abs().max(1e-10).log10()
+}
+
+pub struct CompressorParams {
+    pub threshold: f64,
+    pub ratio: f64,
+    pub attack: f64,
+    pub release: f64,
+    pub knee: f64,
+    pub makeup: f64,
+}
+
+impl Default for CompressorParams {
+    fn default() -> Self {
+        Self { threshold: -18.0, ratio: 4.0, attack: 0.01, release: 0.1, knee: 6.0, makeup: 0.0 }
+    }
+}
+
+impl CompressorParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "threshold" => result.threshold = parse_decibels(kv[1])?,
+                "ratio" => {
+                    result.ratio = kv[1].parse().map_err(|_| eyre!("Invalid ratio value"))?
+                }
+                "attack" => result.attack = parse_duration(kv[1])?,
+                "release" => result.release = parse_duration(kv[1])?,
+                "knee" => result.knee = parse_decibels(kv[1])?,
+                "makeup" => result.makeup = parse_decibels(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.ratio < 1.0 {
+            bail!("compressor ratio must be at least 1");
+        }
+        if result.attack < 0.0 || result.release < 0.0 || result.knee < 0.0 {
+            bail!("compressor attack, release and knee must not be negative");
+        }
+        Ok(result)
+    }
+}
+
+/// Feed-forward compressor with a soft knee. Gain reduction is computed in
+/// dB from the instantaneous level and smoothed with separate attack and
+/// release times.
+pub struct CompressorProcessor {
+    pub threshold: f64,
+    pub ratio: f64,
+    pub attack: f64,
+    pub release: f64,
+    pub knee: f64,
+    pub makeup: f64,
+    sample_rate: f64,
+    gain_reduction: Vec<f64>,
+}
+
+impl CompressorProcessor {
+    #[instrument(level = "debug", fields(threshold = %threshold, ratio = %ratio, attack = %attack, release = %release, knee = %knee, makeup = %makeup))]
+    pub fn new(
+        threshold: f64,
+        ratio: f64,
+        attack: f64,
+        release: f64,
+        knee: f64,
+        makeup: f64,
+    ) -> Self {
+        debug!("Creating compressor at {} dB, ratio {}:1", threshold, ratio);
+        Self {
+            threshold,
+            ratio,
+            attack,
+            release,
+            knee,
+            makeup,
+            sample_rate: 44100.0,
+            gain_reduction: Vec::new(),
+        }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "compressor" {
+            bail!("Not a compressor spec");
+        }
+        let params = CompressorParams::parse(&parts[1..])?;
+        info!("Compressor created at {} dB, ratio {}:1", params.threshold, params.ratio);
+        Ok(Self::new(
+            params.threshold,
+            params.ratio,
+            params.attack,
+            params.release,
+            params.knee,
+            params.makeup,
+        ))
+    }
+
+    /// Static curve: gain change in dB (zero or negative) for an input level.
+    pub fn static_curve(&self, level: f64) -> f64 {
+        let over = level - self.threshold;
+        let slope = 1.0 / self.ratio - 1.0;
+        if 2.0 * over <= -self.knee {
+            0.0
+        } else if 2.0 * over.abs() <= self.knee {
+            slope * (over + self.knee / 2.0).powi(2) / (2.0 * self.knee)
+        } else {
+            slope * over
+        }
+    }
+}
+
+impl Processor for CompressorProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Compressing {} samples", samples.len());
+        let attack = smoothing_coefficient(self.attack, self.sample_rate);
+        let release = smoothing_coefficient(self.release, self.sample_rate);
+
+        let mut reduction = 0.0;
+        let mut deepest: f64 = 0.0;
+        self.gain_reduction.clear();
+        for sample in samples.iter_mut() {
+            let target = self.static_curve(level_db(*sample));
+            // Falling further is the attack phase; recovering is the release
+            let coefficient = if target < reduction { attack } else { release };
+            reduction = coefficient * reduction + (1.0 - coefficient) * target;
+            deepest = deepest.min(reduction);
+            self.gain_reduction.push(reduction);
+            *sample *= 10f64.powf((reduction + self.makeup) / 20.0);
+        }
+        debug!("Compression complete, deepest gain reduction {:.2} dB", deepest);
+    }
+}
+
+impl Component for CompressorProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), threshold = %self.threshold))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through compressor", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    /// Smoothed gain reduction in dB from the most recent run, one value per
+    /// sample; makeup gain is not included.
+    fn gain_reduction(&self) -> Option<&[f64]> {
+        Some(&self.gain_reduction)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "compressor:threshold={}:ratio={}:attack={}:release={}:knee={}:makeup={}",
+            self.threshold, self.ratio, self.attack, self.release, self.knee, self.makeup
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Settled output level in dB for a constant input at `level` dBFS.
+    fn settled_db(spec: &str, level: f64) -> f64 {
+        let mut buffer = vec![10f64.powf(level / 20.0); 44100];
+        let mut compressor = CompressorProcessor::from_spec(spec).unwrap();
+        Component::process(&mut compressor, &mut buffer, 1.0, 44100.0).unwrap();
+        level_db(buffer[44099])
+    }
+
+    #[test]
+    fn levels_above_threshold_are_divided_by_ratio() {
+        let spec = "compressor:threshold=-20:ratio=4:knee=0";
+        // 20 dB over the threshold comes out 5 dB over
+        assert!((settled_db(spec, 0.0) + 15.0).abs() < 0.01);
+        assert!((settled_db(spec, -30.0) + 30.0).abs() < 1e-9);
+        let makeup = settled_db("compressor:threshold=-20:ratio=4:knee=0:makeup=6dB", 0.0);
+        assert!((makeup + 9.0).abs() < 0.01);
+    }
+
+    #[test]
+    fn soft_knee_starts_below_threshold() {
+        let spec = "compressor:threshold=-20:ratio=4:knee=10";
+        // Halfway into the knee: a quarter of the full reduction of 5 dB over
+        let at_threshold = settled_db(spec, -20.0);
+        assert!((at_threshold + 20.0 + 0.9375).abs() < 0.01, "{}", at_threshold);
+        assert!((settled_db(spec, -26.0) + 26.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn static_curve_matches_ratio_and_knee() {
+        let compressor =
+            CompressorProcessor::from_spec("compressor:threshold=-20:ratio=4:knee=10").unwrap();
+        assert_eq!(compressor.static_curve(-40.0), 0.0);
+        assert!((compressor.static_curve(-20.0) + 0.9375).abs() < 1e-12);
+        assert!((compressor.static_curve(0.0) + 15.0).abs() < 1e-12);
+    }
+
+    #[test]
+    fn gain_reduction_is_recorded_per_sample() {
+        let mut compressor =
+            CompressorProcessor::from_spec("compressor:threshold=-20:ratio=4:knee=0:makeup=6")
+                .unwrap();
+        let mut buffer = vec![1.0; 44100];
+        buffer[..22050].fill(0.01);
+        Component::process(&mut compressor, &mut buffer, 1.0, 44100.0).unwrap();
+        let curve = Component::gain_reduction(&compressor).unwrap();
+        assert_eq!(curve.len(), buffer.len());
+        assert_eq!(curve[22049], 0.0);
+        assert!((curve[44099] + 15.0).abs() < 0.01);
+        assert!(curve.iter().all(|&g| g <= 0.0));
+    }
+}
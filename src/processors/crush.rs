@@ -0,0 +1,179 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::random::Rng;
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+pub struct CrushParams {
+    pub bits: u32,
+    pub rate: Option<f64>,
+    pub dither: bool,
+    pub seed: u64,
+}
+
+impl Default for CrushParams {
+    fn default() -> Self {
+        Self { bits: 8, rate: None, dither: false, seed: 0 }
+    }
+}
+
+impl CrushParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "bits" => result.bits = kv[1].parse().map_err(|_| eyre!("Invalid bits value"))?,
+                "rate" => {
+                    result.rate = Some(kv[1].parse().map_err(|_| eyre!("Invalid rate value"))?)
+                }
+                "dither" => {
+                    result.dither = kv[1].parse().map_err(|_| eyre!("Invalid dither value"))?
+                }
+                "seed" => result.seed = kv[1].parse().map_err(|_| eyre!("Invalid seed value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if !(1..=32).contains(&result.bits) {
+            bail!("crush bits must be between 1 and 32");
+        }
+        if result.rate.is_some_and(|rate| rate <= 0.0) {
+            bail!("crush rate must be positive");
+        }
+        Ok(result)
+    }
+}
+
+/// Bitcrusher. Holds each sample for `sample_rate / rate` samples to mimic a
+/// lower sample rate, then rounds to `bits` of resolution, optionally with
+/// seeded TPDF dither so renders stay reproducible.
+pub struct CrushProcessor {
+    pub bits: u32,
+    pub rate: Option<f64>,
+    pub dither: bool,
+    pub seed: u64,
+    sample_rate: f64,
+}
+
+impl CrushProcessor {
+    #[instrument(level = "debug", fields(bits = %bits, rate = ?rate, dither = %dither, seed = %seed))]
+    pub fn new(bits: u32, rate: Option<f64>, dither: bool, seed: u64) -> Self {
+        debug!("Creating {}-bit crusher (rate {:?})", bits, rate);
+        Self { bits, rate, dither, seed, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "crush" {
+            bail!("Not a crush spec");
+        }
+        let params = CrushParams::parse(&parts[1..])?;
+        info!("Crusher created at {} bits", params.bits);
+        Ok(Self::new(params.bits, params.rate, params.dither, params.seed))
+    }
+}
+
+impl Processor for CrushProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Crushing {} samples", samples.len());
+        // Quantisation step around zero, with full scale at ±1
+        let levels = 2f64.powi(self.bits as i32 - 1);
+        let step = self.rate.map_or(1.0, |rate| (rate / self.sample_rate).min(1.0));
+        let mut rng = Rng::new(self.seed);
+
+        let mut phase = 1.0;
+        let mut held = 0.0;
+        for sample in samples.iter_mut() {
+            if phase >= 1.0 {
+                phase -= 1.0;
+                held = *sample;
+            }
+            phase += step;
+
+            let noise = if self.dither { rng.next_f64() - rng.next_f64() } else { 0.0 };
+            *sample = ((held * levels + noise).round() / levels).clamp(-1.0, 1.0);
+        }
+        debug!("Crushing complete");
+    }
+}
+
+impl Component for CrushProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), bits = %self.bits))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through crusher", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        let rate = self.rate.map(|rate| format!(":rate={}", rate)).unwrap_or_default();
+        format!("crush:bits={}{}:dither={}:seed={}", self.bits, rate, self.dither, self.seed)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crush(spec: &str, sample_rate: f64) -> Vec<f64> {
+        let mut buffer: Vec<f64> = (0..1000)
+            .map(|n| 0.9 * (2.0 * std::f64::consts::PI * n as f64 / 250.0).sin())
+            .collect();
+        let mut crusher = CrushProcessor::from_spec(spec).unwrap();
+        Component::process(&mut crusher, &mut buffer, 1.0, sample_rate).unwrap();
+        buffer
+    }
+
+    #[test]
+    fn output_lands_on_bit_depth_grid() {
+        let samples = crush("crush:bits=3", 1000.0);
+        for sample in &samples {
+            assert_eq!((sample * 4.0).fract(), 0.0, "{} is off the 3-bit grid", sample);
+        }
+        let mut levels: Vec<i64> = samples.iter().map(|s| (s * 4.0) as i64).collect();
+        levels.sort();
+        levels.dedup();
+        assert_eq!(levels, [-4, -3, -2, -1, 0, 1, 2, 3, 4]);
+    }
+
+    #[test]
+    fn rate_holds_samples() {
+        let samples = crush("crush:bits=16:rate=250", 1000.0);
+        for block in samples.chunks(4) {
+            assert!(block.iter().all(|&s| s == block[0]));
+        }
+    }
+
+    #[test]
+    fn dither_is_seeded() {
+        let first = crush("crush:bits=4:dither=true:seed=1", 1000.0);
+        assert_eq!(first, crush("crush:bits=4:dither=true:seed=1", 1000.0));
+        assert_ne!(first, crush("crush:bits=4:dither=true:seed=2", 1000.0));
+        assert_ne!(first, crush("crush:bits=4", 1000.0));
+    }
+}
@@ -0,0 +1,96 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+
+/// Steepness of the exponential shapes.
+const K: f64 = 5.0;
+
+/// Shape of a transition between two levels, shared by the envelope and
+/// fade processors so that a curve name means the same thing in both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Curve {
+    Linear,
+    /// Fast start that settles at the end, like an RC charge.
+    Exponential,
+    /// Mirror image of `Exponential`: slow start and fast finish, roughly
+    /// linear in dB.
+    InverseExponential,
+    /// Raised cosine: gentle at both ends.
+    S,
+    /// Sine law, so a fall and a rise overlapped as a crossfade keep
+    /// constant power.
+    EqualPower,
+}
+
+impl Curve {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "linear" | "lin" => Ok(Self::Linear),
+            "exp" | "exponential" => Ok(Self::Exponential),
+            "inv_exp" => Ok(Self::InverseExponential),
+            "s" => Ok(Self::S),
+            "equal_power" => Ok(Self::EqualPower),
+            _ => bail!("Unknown curve: {} (expected linear, exp, inv_exp, s or equal_power)", name),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::Exponential => "exp",
+            Self::InverseExponential => "inv_exp",
+            Self::S => "s",
+            Self::EqualPower => "equal_power",
+        }
+    }
+
+    /// Progress through the transition at `x`, rising from 0 at `x = 0` to 1
+    /// at `x = 1`.
+    pub fn shape(&self, x: f64) -> f64 {
+        let x = x.clamp(0.0, 1.0);
+        match self {
+            Self::Linear => x,
+            Self::Exponential => (1.0 - (-K * x).exp()) / (1.0 - (-K).exp()),
+            Self::InverseExponential => ((K * x).exp() - 1.0) / (K.exp() - 1.0),
+            Self::S => 0.5 - 0.5 * (PI * x).cos(),
+            Self::EqualPower => (0.5 * PI * x).sin(),
+        }
+    }
+
+    /// Moves from `from` to `to` as `x` goes from 0 to 1.
+    pub fn interpolate(&self, from: f64, to: f64, x: f64) -> f64 {
+        from + (to - from) * self.shape(x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shapes_run_from_zero_to_one() {
+        for name in ["linear", "exp", "inv_exp", "s", "equal_power"] {
+            let curve = Curve::parse(name).unwrap();
+            assert_eq!(curve.name(), name);
+            assert!(curve.shape(0.0).abs() < 1e-12, "{} starts at {}", name, curve.shape(0.0));
+            assert!(
+                (curve.shape(1.0) - 1.0).abs() < 1e-12,
+                "{} ends at {}",
+                name,
+                curve.shape(1.0)
+            );
+        }
+    }
+
+    #[test]
+    fn exp_starts_fast_and_inv_exp_starts_slow() {
+        assert!(Curve::Exponential.shape(0.5) > 0.5);
+        assert!(Curve::InverseExponential.shape(0.5) < 0.5);
+        // Mirror images of each other
+        for x in [0.1, 0.3, 0.7] {
+            let mirrored = 1.0 - Curve::Exponential.shape(1.0 - x);
+            assert!((Curve::InverseExponential.shape(x) - mirrored).abs() < 1e-12);
+        }
+    }
+}
@@ -0,0 +1,238 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::parser::parse_duration;
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+/// Longest tail a delay will append to the buffer, in seconds.
+const MAX_TAIL: f64 = 30.0;
+
+/// Circular buffer read at fractional positions with 4-point Hermite
+/// interpolation, shared by the delay-based effects.
+#[derive(Debug, Clone)]
+pub struct DelayLine {
+    buffer: Vec<f64>,
+    write_index: usize,
+}
+
+impl DelayLine {
+    /// A line able to delay by up to `max_delay` samples.
+    pub fn new(max_delay: usize) -> Self {
+        Self { buffer: vec![0.0; max_delay + 4], write_index: 0 }
+    }
+
+    pub fn write(&mut self, sample: f64) {
+        self.buffer[self.write_index] = sample;
+        self.write_index = (self.write_index + 1) % self.buffer.len();
+    }
+
+    /// Sample written `delay` samples before the next write; `delay` is
+    /// clamped to the capacity of the line and must be at least 1.
+    pub fn read(&self, delay: f64) -> f64 {
+        let len = self.buffer.len();
+        let delay = delay.clamp(1.0, (len - 3) as f64);
+        let whole = delay.floor() as usize;
+        let frac = delay - whole as f64;
+        let at = |offset: usize| self.buffer[(self.write_index + len * 2 - offset) % len];
+
+        // Newest to oldest; nothing newer than one sample has been written yet
+        let x0 = if whole > 1 { at(whole - 1) } else { at(whole) };
+        let x1 = at(whole);
+        let x2 = at(whole + 1);
+        let x3 = at(whole + 2);
+        let c1 = 0.5 * (x2 - x0);
+        let c2 = x0 - 2.5 * x1 + 2.0 * x2 - 0.5 * x3;
+        let c3 = 0.5 * (x3 - x0) + 1.5 * (x1 - x2);
+        ((c3 * frac + c2) * frac + c1) * frac + x1
+    }
+}
+
+pub struct DelayParams {
+    pub time: f64,
+    pub feedback: f64,
+    pub mix: f64,
+    pub damp: f64,
+    pub tail: bool,
+}
+
+impl Default for DelayParams {
+    fn default() -> Self {
+        Self { time: 0.25, feedback: 0.4, mix: 0.5, damp: 0.0, tail: false }
+    }
+}
+
+impl DelayParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "time" => result.time = parse_duration(kv[1])?,
+                "feedback" => {
+                    result.feedback = kv[1].parse().map_err(|_| eyre!("Invalid feedback value"))?
+                }
+                "mix" => result.mix = kv[1].parse().map_err(|_| eyre!("Invalid mix value"))?,
+                "damp" => result.damp = kv[1].parse().map_err(|_| eyre!("Invalid damp value"))?,
+                "tail" => result.tail = kv[1].parse().map_err(|_| eyre!("Invalid tail value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.time <= 0.0 {
+            bail!("delay time must be positive");
+        }
+        if result.feedback.abs() >= 1.0 {
+            bail!("delay feedback must be between -1 and 1 (exclusive)");
+        }
+        if !(0.0..=1.0).contains(&result.mix) || !(0.0..=1.0).contains(&result.damp) {
+            bail!("delay mix and damp must be between 0 and 1");
+        }
+        Ok(result)
+    }
+}
+
+/// Feedback echo. Repeats pass through a one-pole lowpass (`damp`) so they
+/// darken as they decay, and with `tail=true` the buffer is extended until
+/// the repeats have died away by 60 dB. There is no ping-pong mode: the
+/// pipeline carries a single mono buffer, so there are no sides to bounce
+/// the repeats between.
+pub struct DelayProcessor {
+    pub time: f64,
+    pub feedback: f64,
+    pub mix: f64,
+    pub damp: f64,
+    pub tail: bool,
+    sample_rate: f64,
+}
+
+impl DelayProcessor {
+    #[instrument(level = "debug", fields(time = %time, feedback = %feedback, mix = %mix, damp = %damp, tail = %tail))]
+    pub fn new(time: f64, feedback: f64, mix: f64, damp: f64, tail: bool) -> Self {
+        debug!("Creating delay of {} s with feedback {}", time, feedback);
+        Self { time, feedback, mix, damp, tail, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "delay" {
+            bail!("Not a delay spec");
+        }
+        let params = DelayParams::parse(&parts[1..])?;
+        info!("Delay processor created with time {} s", params.time);
+        Ok(Self::new(params.time, params.feedback, params.mix, params.damp, params.tail))
+    }
+
+    /// Time in seconds for the repeats to fall 60 dB below the input.
+    pub fn tail_length(&self) -> f64 {
+        let repeats = if self.feedback.abs() < 1e-3 {
+            1.0
+        } else {
+            (-3.0 / self.feedback.abs().log10()).ceil() + 1.0
+        };
+        (repeats * self.time).min(MAX_TAIL)
+    }
+}
+
+impl Processor for DelayProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        let delay = (self.time * self.sample_rate).max(1.0);
+        debug!("Applying {} sample delay to {} samples", delay, samples.len());
+
+        let mut line = DelayLine::new(delay.ceil() as usize);
+        let mut damped = 0.0;
+        for sample in samples.iter_mut() {
+            let dry = *sample;
+            let delayed = line.read(delay);
+            damped = (1.0 - self.damp) * delayed + self.damp * damped;
+            line.write(dry + self.feedback * damped);
+            *sample = (1.0 - self.mix) * dry + self.mix * delayed;
+        }
+        debug!("Delay processing complete");
+    }
+}
+
+impl Component for DelayProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), time = %self.time))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        self.sample_rate = sample_rate;
+        if self.tail {
+            let extra = (self.tail_length() * sample_rate).ceil() as usize;
+            debug!("Extending buffer by {} samples for the delay tail", extra);
+            buffer.resize(buffer.len() + extra, 0.0);
+        }
+        debug!("Processing {} samples through delay", buffer.len());
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "delay:time={}:feedback={}:mix={}:damp={}:tail={}",
+            self.time, self.feedback, self.mix, self.damp, self.tail
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn repeats_decay_by_feedback() {
+        let mut buffer = vec![0.0; 1000];
+        buffer[0] = 1.0;
+        let mut delay = DelayProcessor::from_spec("delay:time=100ms:feedback=0.5:mix=1").unwrap();
+        Component::process(&mut delay, &mut buffer, 0.1, 1000.0).unwrap();
+        assert_eq!(buffer[0], 0.0);
+        for (repeat, expected) in [(1, 1.0), (2, 0.5), (3, 0.25)] {
+            assert!((buffer[repeat * 100] - expected).abs() < 1e-12, "repeat {}", repeat);
+        }
+    }
+
+    #[test]
+    fn tail_extends_buffer_until_repeats_fall_60_db() {
+        let mut delay =
+            DelayProcessor::from_spec("delay:time=100ms:feedback=0.5:tail=true").unwrap();
+        // 0.5^10 is the first repeat below -60 dB, plus the first echo
+        assert!((delay.tail_length() - 1.1).abs() < 1e-12);
+        let mut buffer = vec![0.0; 1000];
+        buffer[0] = 1.0;
+        Component::process(&mut delay, &mut buffer, 1.0, 1000.0).unwrap();
+        assert_eq!(buffer.len(), 2100);
+        let last = buffer[2000..].iter().fold(0.0_f64, |m, s| m.max(s.abs()));
+        assert!(last < 1e-3, "tail ends at {}", last);
+
+        let mut untailed = vec![0.0; 1000];
+        Component::process(
+            &mut DelayProcessor::from_spec("delay").unwrap(),
+            &mut untailed,
+            1.0,
+            1000.0,
+        )
+        .unwrap();
+        assert_eq!(untailed.len(), 1000);
+    }
+}
@@ -0,0 +1,298 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+/// Filter taps per unit of oversampling factor on each side of the centre.
+const TAPS_PER_FACTOR: usize = 16;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shape {
+    Tanh,
+    Hard,
+    Fold,
+    Cubic,
+}
+
+impl Shape {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "tanh" => Ok(Self::Tanh),
+            "hard" => Ok(Self::Hard),
+            "fold" => Ok(Self::Fold),
+            "cubic" => Ok(Self::Cubic),
+            _ => bail!("Unknown distortion type: {} (expected tanh, hard, fold or cubic)", value),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Tanh => "tanh",
+            Self::Hard => "hard",
+            Self::Fold => "fold",
+            Self::Cubic => "cubic",
+        }
+    }
+
+    /// Transfer curve, mapping `[-1, 1]` onto itself before drive.
+    pub fn apply(&self, x: f64) -> f64 {
+        match self {
+            Self::Tanh => x.tanh(),
+            Self::Hard => x.clamp(-1.0, 1.0),
+            // Reflect off ±1 like a triangle wave of period 4
+            Self::Fold => {
+                let phase = (x + 1.0).rem_euclid(4.0);
+                if phase < 2.0 { phase - 1.0 } else { 3.0 - phase }
+            }
+            Self::Cubic => {
+                let x = x.clamp(-1.0, 1.0);
+                1.5 * x - 0.5 * x * x * x
+            }
+        }
+    }
+}
+
+/// Linear-phase windowed-sinc FIR used both to interpolate up to and
+/// decimate down from the oversampled rate.
+struct Oversampler {
+    factor: usize,
+    taps: Vec<f64>,
+}
+
+impl Oversampler {
+    fn new(factor: usize) -> Self {
+        let half = TAPS_PER_FACTOR * factor;
+        let len = 2 * half + 1;
+        // Cut off a little below the original Nyquist frequency
+        let cutoff = 0.45 / factor as f64;
+        let taps = (0..len)
+            .map(|n| {
+                let x = n as f64 - half as f64;
+                let sinc = if x == 0.0 {
+                    1.0
+                } else {
+                    (2.0 * PI * cutoff * x).sin() / (2.0 * PI * cutoff * x)
+                };
+                let window = 0.42 - 0.5 * (2.0 * PI * n as f64 / (len - 1) as f64).cos()
+                    + 0.08 * (4.0 * PI * n as f64 / (len - 1) as f64).cos();
+                2.0 * cutoff * sinc * window
+            })
+            .collect();
+        Self { factor, taps }
+    }
+
+    fn upsample(&self, samples: &[f64]) -> Vec<f64> {
+        let half = self.taps.len() / 2;
+        let len = samples.len() * self.factor;
+        (0..len)
+            .map(|j| {
+                // Only every `factor`-th input of the zero-stuffed signal is non-zero
+                let centre = j + half;
+                let mut sum = 0.0;
+                let mut k = centre % self.factor;
+                while k < self.taps.len() {
+                    // Taps reaching before the first input see silence
+                    if let Some(sample) =
+                        centre.checked_sub(k).and_then(|offset| samples.get(offset / self.factor))
+                    {
+                        sum += self.taps[k] * sample;
+                    }
+                    k += self.factor;
+                }
+                sum * self.factor as f64
+            })
+            .collect()
+    }
+
+    fn downsample(&self, samples: &[f64]) -> Vec<f64> {
+        let half = self.taps.len() / 2;
+        (0..samples.len() / self.factor)
+            .map(|i| {
+                let centre = i * self.factor + half;
+                self.taps
+                    .iter()
+                    .enumerate()
+                    .filter_map(|(k, tap)| {
+                        centre.checked_sub(k).and_then(|j| samples.get(j)).map(|s| tap * s)
+                    })
+                    .sum()
+            })
+            .collect()
+    }
+}
+
+pub struct DistortParams {
+    pub shape: Shape,
+    pub drive: f64,
+    pub mix: f64,
+    pub oversample: usize,
+}
+
+impl Default for DistortParams {
+    fn default() -> Self {
+        Self { shape: Shape::Tanh, drive: 2.0, mix: 1.0, oversample: 4 }
+    }
+}
+
+impl DistortParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "type" => result.shape = Shape::parse(kv[1])?,
+                "drive" => {
+                    result.drive = kv[1].parse().map_err(|_| eyre!("Invalid drive value"))?
+                }
+                "mix" => result.mix = kv[1].parse().map_err(|_| eyre!("Invalid mix value"))?,
+                "oversample" => {
+                    result.oversample =
+                        kv[1].parse().map_err(|_| eyre!("Invalid oversample value"))?
+                }
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.drive <= 0.0 {
+            bail!("distort drive must be positive");
+        }
+        if !(0.0..=1.0).contains(&result.mix) {
+            bail!("distort mix must be between 0 and 1");
+        }
+        if ![1, 2, 4, 8].contains(&result.oversample) {
+            bail!("distort oversample must be 1, 2, 4 or 8");
+        }
+        Ok(result)
+    }
+}
+
+/// Waveshaper. The signal is multiplied by `drive`, shaped, and blended
+/// with the dry signal by `mix`, all at `oversample` times the sample rate
+/// so that the harmonics it creates fold back far less.
+pub struct DistortProcessor {
+    pub shape: Shape,
+    pub drive: f64,
+    pub mix: f64,
+    pub oversample: usize,
+}
+
+impl DistortProcessor {
+    #[instrument(level = "debug", fields(shape = ?shape, drive = %drive, mix = %mix, oversample = %oversample))]
+    pub fn new(shape: Shape, drive: f64, mix: f64, oversample: usize) -> Self {
+        debug!(
+            "Creating {} distortion with drive {} ({}x oversampling)",
+            shape.name(),
+            drive,
+            oversample
+        );
+        Self { shape, drive, mix, oversample }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "distort" {
+            bail!("Not a distort spec");
+        }
+        let params = DistortParams::parse(&parts[1..])?;
+        info!("Distortion created: {} with drive {}", params.shape.name(), params.drive);
+        Ok(Self::new(params.shape, params.drive, params.mix, params.oversample))
+    }
+}
+
+impl Processor for DistortProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), shape = ?self.shape))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Distorting {} samples", samples.len());
+        let shaped = if self.oversample > 1 {
+            let oversampler = Oversampler::new(self.oversample);
+            let mut upsampled = oversampler.upsample(samples);
+            for sample in upsampled.iter_mut() {
+                *sample = self.shape.apply(self.drive * *sample);
+            }
+            oversampler.downsample(&upsampled)
+        } else {
+            samples.iter().map(|&s| self.shape.apply(self.drive * s)).collect()
+        };
+
+        for (sample, wet) in samples.iter_mut().zip(shaped) {
+            *sample = (1.0 - self.mix) * *sample + self.mix * wet;
+        }
+        debug!("Distortion complete");
+    }
+}
+
+impl Component for DistortProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), shape = ?self.shape))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, _sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through distortion", buffer.len());
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "distort:type={}:drive={}:mix={}:oversample={}",
+            self.shape.name(),
+            self.drive,
+            self.mix,
+            self.oversample
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn oversampled_sine_keeps_length_and_level() {
+        let sample_rate = 44100.0;
+        let input: Vec<f64> =
+            (0..4410).map(|i| 0.8 * (2.0 * PI * 1000.0 * i as f64 / sample_rate).sin()).collect();
+        // tanh(drive * 0.8) for the default drive of 2
+        let expected = 1.6f64.tanh();
+
+        for oversample in [2, 4, 8] {
+            let mut distort =
+                DistortProcessor::from_spec(&format!("distort:oversample={}", oversample)).unwrap();
+            let mut samples = input.clone();
+            Processor::process(&mut distort, &mut samples);
+
+            assert_eq!(samples.len(), input.len());
+            let peak = samples.iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+            let settled = samples[1000..3000].iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+            assert!(peak <= 1.0, "{}x: peak {} exceeds full scale", oversample, peak);
+            assert!(
+                (settled - expected).abs() < 0.05,
+                "{}x: settled peak {} differs from {}",
+                oversample,
+                settled,
+                expected
+            );
+        }
+    }
+}
@@ -0,0 +1,229 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::Curve;
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum EnvelopeShape {
+    Adsr { attack: f64, decay: f64, sustain: f64, release: f64 },
+    Breakpoints(Vec<(f64, f64)>),
+}
+
+pub struct EnvelopeParams {
+    pub shape: EnvelopeShape,
+    pub curve: Curve,
+}
+
+impl Default for EnvelopeParams {
+    fn default() -> Self {
+        Self {
+            shape: EnvelopeShape::Adsr { attack: 0.01, decay: 0.1, sustain: 0.8, release: 0.2 },
+            curve: Curve::Linear,
+        }
+    }
+}
+
+impl EnvelopeParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let (mut attack, mut decay, mut sustain, mut release) = (0.01, 0.1, 0.8, 0.2);
+        let mut points = None;
+        let mut curve = Curve::Linear;
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "attack" => attack = kv[1].parse().map_err(|_| eyre!("Invalid attack value"))?,
+                "decay" => decay = kv[1].parse().map_err(|_| eyre!("Invalid decay value"))?,
+                "sustain" => sustain = kv[1].parse().map_err(|_| eyre!("Invalid sustain value"))?,
+                "release" => release = kv[1].parse().map_err(|_| eyre!("Invalid release value"))?,
+                "points" => points = Some(parse_points(kv[1])?),
+                "curve" => curve = Curve::parse(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if attack < 0.0 || decay < 0.0 || release < 0.0 {
+            bail!("Envelope times must not be negative");
+        }
+        if !(0.0..=1.0).contains(&sustain) {
+            bail!("Envelope sustain must be between 0 and 1");
+        }
+
+        let shape = match points {
+            Some(points) => EnvelopeShape::Breakpoints(points),
+            None => EnvelopeShape::Adsr { attack, decay, sustain, release },
+        };
+        Ok(Self { shape, curve })
+    }
+}
+
+/// Parses `[time/level,time/level,...]` breakpoints, times in seconds.
+fn parse_points(value: &str) -> Result<Vec<(f64, f64)>> {
+    let inner = value
+        .strip_prefix('[')
+        .and_then(|v| v.strip_suffix(']'))
+        .ok_or_else(|| eyre!("envelope points must be enclosed in [ ]"))?;
+
+    let mut points = Vec::new();
+    for point in inner.split(',').map(|p| p.trim()).filter(|p| !p.is_empty()) {
+        let (time, level) =
+            point.split_once('/').ok_or_else(|| eyre!("Invalid envelope point: {}", point))?;
+        let time: f64 = time.parse().map_err(|_| eyre!("Invalid point time: {}", time))?;
+        let level: f64 = level.parse().map_err(|_| eyre!("Invalid point level: {}", level))?;
+        points.push((time, level));
+    }
+
+    if points.is_empty() {
+        bail!("envelope requires at least one point");
+    }
+    if points.windows(2).any(|w| w[1].0 < w[0].0) {
+        bail!("envelope point times must be increasing");
+    }
+    Ok(points)
+}
+
+pub struct EnvelopeProcessor {
+    pub shape: EnvelopeShape,
+    pub curve: Curve,
+    sample_rate: f64,
+}
+
+impl EnvelopeProcessor {
+    #[instrument(level = "debug", fields(shape = ?shape, curve = ?curve))]
+    pub fn new(shape: EnvelopeShape, curve: Curve) -> Self {
+        debug!("Creating envelope processor: {:?} ({} curve)", shape, curve.name());
+        Self { shape, curve, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "envelope" {
+            bail!("Not an envelope spec");
+        }
+        let params = EnvelopeParams::parse(&parts[1..])?;
+        info!("Envelope processor created: {:?}", params.shape);
+        Ok(Self::new(params.shape, params.curve))
+    }
+
+    /// Gain of the envelope at time `t` for a buffer lasting `total` seconds.
+    pub fn gain_at(&self, t: f64, total: f64) -> f64 {
+        match &self.shape {
+            EnvelopeShape::Adsr { attack, decay, sustain, release } => {
+                let held = |t: f64| {
+                    if t < *attack {
+                        self.curve.interpolate(0.0, 1.0, t / attack)
+                    } else if t < attack + decay {
+                        self.curve.interpolate(1.0, *sustain, (t - attack) / decay)
+                    } else {
+                        *sustain
+                    }
+                };
+                let release_start = (total - release).max(0.0);
+                if t < release_start {
+                    held(t)
+                } else {
+                    let level = held(release_start);
+                    self.curve.interpolate(level, 0.0, (t - release_start) / release)
+                }
+            }
+            EnvelopeShape::Breakpoints(points) => {
+                let next = points.iter().position(|&(time, _)| time > t);
+                match next {
+                    None => points[points.len() - 1].1,
+                    Some(0) => points[0].1,
+                    Some(i) => {
+                        let (t0, l0) = points[i - 1];
+                        let (t1, l1) = points[i];
+                        self.curve.interpolate(l0, l1, (t - t0) / (t1 - t0))
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Processor for EnvelopeProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying envelope to {} samples", samples.len());
+        let total = samples.len() as f64 / self.sample_rate;
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample *= self.gain_at(i as f64 / self.sample_rate, total);
+        }
+        debug!("Envelope processing complete");
+    }
+}
+
+impl Component for EnvelopeProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len()))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through envelope processor", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        match &self.shape {
+            EnvelopeShape::Adsr { attack, decay, sustain, release } => format!(
+                "envelope:attack={}:decay={}:sustain={}:release={}:curve={}",
+                attack,
+                decay,
+                sustain,
+                release,
+                self.curve.name()
+            ),
+            EnvelopeShape::Breakpoints(points) => {
+                let points: Vec<String> =
+                    points.iter().map(|(time, level)| format!("{}/{}", time, level)).collect();
+                format!("envelope:points=[{}]:curve={}", points.join(","), self.curve.name())
+            }
+        }
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn sustain_must_be_a_level() {
+        assert!(EnvelopeProcessor::from_spec("envelope:sustain=0").is_ok());
+        assert!(EnvelopeProcessor::from_spec("envelope:sustain=1").is_ok());
+        assert!(EnvelopeProcessor::from_spec("envelope:sustain=-1").is_err());
+        assert!(EnvelopeProcessor::from_spec("envelope:sustain=1.5").is_err());
+    }
+
+    #[test]
+    fn exp_decay_falls_fast_then_settles() {
+        let envelope =
+            EnvelopeProcessor::from_spec("envelope:attack=0:decay=1:sustain=0:release=0:curve=exp")
+                .unwrap();
+        // (e^-2.5 - e^-5) / (1 - e^-5) of the way from 0 back towards 1
+        assert!((envelope.gain_at(0.5, 10.0) - 0.07586).abs() < 1e-5);
+        assert!(envelope.gain_at(0.1, 10.0) < 0.61);
+        assert_eq!(envelope.gain_at(2.0, 10.0), 0.0);
+    }
+}
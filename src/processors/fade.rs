@@ -0,0 +1,129 @@
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::Curve;
+use crate::parser::parse_duration;
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+pub struct FadeParams {
+    pub fade_in: f64,
+    pub fade_out: f64,
+    pub curve: Curve,
+}
+
+impl Default for FadeParams {
+    fn default() -> Self {
+        Self { fade_in: 0.01, fade_out: 0.01, curve: Curve::Linear }
+    }
+}
+
+impl FadeParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "in" => result.fade_in = parse_duration(kv[1])?,
+                "out" => result.fade_out = parse_duration(kv[1])?,
+                "curve" => result.curve = Curve::parse(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.fade_in < 0.0 || result.fade_out < 0.0 {
+            bail!("fade in and out must not be negative");
+        }
+        Ok(result)
+    }
+}
+
+/// Fades the start and end of the buffer. Fade-outs play the curve
+/// reversed in time, so `exp` rises quickly and drops away late while
+/// `inv_exp` creeps in and tails off gently. Fades longer than the buffer
+/// overlap and multiply.
+pub struct FadeProcessor {
+    pub fade_in: f64,
+    pub fade_out: f64,
+    pub curve: Curve,
+    sample_rate: f64,
+}
+
+impl FadeProcessor {
+    #[instrument(level = "debug", fields(fade_in = %fade_in, fade_out = %fade_out, curve = ?curve))]
+    pub fn new(fade_in: f64, fade_out: f64, curve: Curve) -> Self {
+        debug!("Creating {} fade ({} s in, {} s out)", curve.name(), fade_in, fade_out);
+        Self { fade_in, fade_out, curve, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "fade" {
+            bail!("Not a fade spec");
+        }
+        let params = FadeParams::parse(&parts[1..])?;
+        info!("Fade created: {} s in, {} s out", params.fade_in, params.fade_out);
+        Ok(Self::new(params.fade_in, params.fade_out, params.curve))
+    }
+
+    /// Gain at time `t` into a buffer `total` seconds long.
+    pub fn gain_at(&self, t: f64, total: f64) -> f64 {
+        let mut gain = 1.0;
+        if self.fade_in > 0.0 && t < self.fade_in {
+            gain *= self.curve.shape(t / self.fade_in);
+        }
+        let remaining = total - t;
+        if self.fade_out > 0.0 && remaining < self.fade_out {
+            gain *= self.curve.shape(remaining / self.fade_out);
+        }
+        gain
+    }
+}
+
+impl Processor for FadeProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying fades to {} samples", samples.len());
+        // The last sample sits at `total`, so the fade-out reaches silence on it
+        let total = samples.len().saturating_sub(1) as f64 / self.sample_rate;
+        for (i, sample) in samples.iter_mut().enumerate() {
+            *sample *= self.gain_at(i as f64 / self.sample_rate, total);
+        }
+        debug!("Fade processing complete");
+    }
+}
+
+impl Component for FadeProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len()))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        if self.fade_in + self.fade_out > duration {
+            debug!("Fades are longer than the {} s buffer and will overlap", duration);
+        }
+        debug!("Processing {} samples through fade", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!("fade:in={}:out={}:curve={}", self.fade_in, self.fade_out, self.curve.name())
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
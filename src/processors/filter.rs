@@ -0,0 +1,324 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FilterType {
+    Lowpass,
+    Highpass,
+    Bandpass,
+    Notch,
+    LowShelf,
+    HighShelf,
+    PeakEq,
+}
+
+impl FilterType {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "lowpass" => Ok(Self::Lowpass),
+            "highpass" => Ok(Self::Highpass),
+            "bandpass" => Ok(Self::Bandpass),
+            "notch" => Ok(Self::Notch),
+            "lowshelf" => Ok(Self::LowShelf),
+            "highshelf" => Ok(Self::HighShelf),
+            "peak_eq" => Ok(Self::PeakEq),
+            _ => bail!("Unknown filter type: {}", name),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Lowpass => "lowpass",
+            Self::Highpass => "highpass",
+            Self::Bandpass => "bandpass",
+            Self::Notch => "notch",
+            Self::LowShelf => "lowshelf",
+            Self::HighShelf => "highshelf",
+            Self::PeakEq => "peak_eq",
+        }
+    }
+
+    /// Whether the `gain` parameter changes the response.
+    pub fn uses_gain(&self) -> bool {
+        matches!(self, Self::LowShelf | Self::HighShelf | Self::PeakEq)
+    }
+}
+
+/// A single second-order section in transposed direct form II, with
+/// coefficients normalised so that `a0 = 1`.
+#[derive(Debug, Clone)]
+pub struct Biquad {
+    b0: f64,
+    b1: f64,
+    b2: f64,
+    a1: f64,
+    a2: f64,
+    z1: f64,
+    z2: f64,
+}
+
+impl Biquad {
+    pub fn from_coefficients(b: [f64; 3], a: [f64; 3]) -> Self {
+        Self {
+            b0: b[0] / a[0],
+            b1: b[1] / a[0],
+            b2: b[2] / a[0],
+            a1: a[1] / a[0],
+            a2: a[2] / a[0],
+            z1: 0.0,
+            z2: 0.0,
+        }
+    }
+
+    /// Coefficients from Robert Bristow-Johnson's Audio EQ Cookbook. `gain`
+    /// is in dB and only affects the shelves and the peaking filter; for the
+    /// shelves `q` plays the role of the cookbook's slope-derived Q.
+    pub fn new(
+        filter_type: FilterType,
+        frequency: f64,
+        q: f64,
+        gain: f64,
+        sample_rate: f64,
+    ) -> Self {
+        let w0 = 2.0 * PI * frequency / sample_rate;
+        let (sin, cos) = w0.sin_cos();
+        let alpha = sin / (2.0 * q);
+        let a = 10f64.powf(gain / 40.0);
+
+        let (b, a) = match filter_type {
+            FilterType::Lowpass => (
+                [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+                [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+            ),
+            FilterType::Highpass => (
+                [(1.0 + cos) / 2.0, -(1.0 + cos), (1.0 + cos) / 2.0],
+                [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+            ),
+            // Constant 0 dB peak gain
+            FilterType::Bandpass => ([alpha, 0.0, -alpha], [1.0 + alpha, -2.0 * cos, 1.0 - alpha]),
+            FilterType::Notch => ([1.0, -2.0 * cos, 1.0], [1.0 + alpha, -2.0 * cos, 1.0 - alpha]),
+            FilterType::PeakEq => (
+                [1.0 + alpha * a, -2.0 * cos, 1.0 - alpha * a],
+                [1.0 + alpha / a, -2.0 * cos, 1.0 - alpha / a],
+            ),
+            FilterType::LowShelf => {
+                let k = 2.0 * a.sqrt() * alpha;
+                (
+                    [
+                        a * ((a + 1.0) - (a - 1.0) * cos + k),
+                        2.0 * a * ((a - 1.0) - (a + 1.0) * cos),
+                        a * ((a + 1.0) - (a - 1.0) * cos - k),
+                    ],
+                    [
+                        (a + 1.0) + (a - 1.0) * cos + k,
+                        -2.0 * ((a - 1.0) + (a + 1.0) * cos),
+                        (a + 1.0) + (a - 1.0) * cos - k,
+                    ],
+                )
+            }
+            FilterType::HighShelf => {
+                let k = 2.0 * a.sqrt() * alpha;
+                (
+                    [
+                        a * ((a + 1.0) + (a - 1.0) * cos + k),
+                        -2.0 * a * ((a - 1.0) + (a + 1.0) * cos),
+                        a * ((a + 1.0) + (a - 1.0) * cos - k),
+                    ],
+                    [
+                        (a + 1.0) - (a - 1.0) * cos + k,
+                        2.0 * ((a - 1.0) - (a + 1.0) * cos),
+                        (a + 1.0) - (a - 1.0) * cos - k,
+                    ],
+                )
+            }
+        };
+        Self::from_coefficients(b, a)
+    }
+
+    pub fn process_sample(&mut self, input: f64) -> f64 {
+        let output = self.b0 * input + self.z1;
+        self.z1 = self.b1 * input - self.a1 * output + self.z2;
+        self.z2 = self.b2 * input - self.a2 * output;
+        output
+    }
+
+    pub fn reset(&mut self) {
+        self.z1 = 0.0;
+        self.z2 = 0.0;
+    }
+}
+
+pub struct FilterParams {
+    pub freq: f64,
+    pub q: f64,
+    pub gain: f64,
+    pub order: usize,
+}
+
+impl Default for FilterParams {
+    fn default() -> Self {
+        Self { freq: 1000.0, q: std::f64::consts::FRAC_1_SQRT_2, gain: 0.0, order: 1 }
+    }
+}
+
+impl FilterParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "q" => result.q = kv[1].parse().map_err(|_| eyre!("Invalid q value"))?,
+                "gain" => result.gain = kv[1].parse().map_err(|_| eyre!("Invalid gain value"))?,
+                "order" => {
+                    result.order = kv[1].parse().map_err(|_| eyre!("Invalid order value"))?
+                }
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.freq <= 0.0 || result.q <= 0.0 {
+            bail!("Filter freq and q must be positive");
+        }
+        if result.order == 0 {
+            bail!("Filter order must be at least 1");
+        }
+        Ok(result)
+    }
+}
+
+/// Biquad filter, optionally cascaded `order` times for steeper slopes (each
+/// stage adds 12 dB/octave to a lowpass or highpass).
+pub struct FilterProcessor {
+    pub filter_type: FilterType,
+    pub frequency: f64,
+    pub q: f64,
+    pub gain: f64,
+    pub order: usize,
+    sample_rate: f64,
+}
+
+impl FilterProcessor {
+    #[instrument(level = "debug", fields(filter_type = ?filter_type, frequency = %frequency, q = %q, gain = %gain, order = %order))]
+    pub fn new(filter_type: FilterType, frequency: f64, q: f64, gain: f64, order: usize) -> Self {
+        debug!("Creating {} filter at {} Hz (order {})", filter_type.name(), frequency, order);
+        Self { filter_type, frequency, q, gain, order, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        let filter_type = FilterType::parse(parts[0])?;
+        let params = FilterParams::parse(&parts[1..])?;
+        info!("{} filter created at {} Hz", filter_type.name(), params.freq);
+        Ok(Self::new(filter_type, params.freq, params.q, params.gain, params.order))
+    }
+}
+
+impl Processor for FilterProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying {} filter to {} samples", self.filter_type.name(), samples.len());
+        let stage =
+            Biquad::new(self.filter_type, self.frequency, self.q, self.gain, self.sample_rate);
+        let mut stages = vec![stage; self.order];
+        for sample in samples.iter_mut() {
+            *sample = stages.iter_mut().fold(*sample, |x, stage| stage.process_sample(x));
+        }
+        debug!("Filter processing complete");
+    }
+}
+
+impl Component for FilterProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), filter_type = ?self.filter_type))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        if self.frequency >= sample_rate / 2.0 {
+            bail!(
+                "{} freq {} Hz must be below Nyquist ({} Hz)",
+                self.filter_type.name(),
+                self.frequency,
+                sample_rate / 2.0
+            );
+        }
+        debug!("Processing {} samples through {} filter", buffer.len(), self.filter_type.name());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        let gain = if self.filter_type.uses_gain() {
+            format!(":gain={}", self.gain)
+        } else {
+            String::new()
+        };
+        format!(
+            "{}:freq={}:q={}{}:order={}",
+            self.filter_type.name(),
+            self.frequency,
+            self.q,
+            gain,
+            self.order
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Steady-state gain in dB of `spec` for a sine at `freq`.
+    fn gain_db(spec: &str, freq: f64) -> f64 {
+        let sr = 44100.0;
+        let mut buffer: Vec<f64> =
+            (0..44100).map(|n| (2.0 * PI * freq * n as f64 / sr).sin()).collect();
+        let mut filter = FilterProcessor::from_spec(spec).unwrap();
+        Component::process(&mut filter, &mut buffer, 1.0, sr).unwrap();
+        let settled = &buffer[22050..];
+        let rms = (settled.iter().map(|s| s * s).sum::<f64>() / settled.len() as f64).sqrt();
+        20.0 * (rms * 2.0_f64.sqrt()).log10()
+    }
+
+    #[test]
+    fn butterworth_lowpass_is_3_db_down_at_cutoff() {
+        let at_cutoff = gain_db("lowpass:freq=1000", 1000.0);
+        assert!((at_cutoff + 3.01).abs() < 0.05, "gain at cutoff {} dB", at_cutoff);
+        assert!(gain_db("lowpass:freq=1000", 100.0).abs() < 0.05);
+        // Second-order slope: 40 dB per decade well above cutoff
+        let decade = gain_db("lowpass:freq=1000", 10000.0);
+        assert!(decade < -38.0 && decade > -44.0, "gain a decade up {} dB", decade);
+    }
+
+    #[test]
+    fn order_cascades_sections() {
+        let doubled = gain_db("lowpass:freq=1000:order=2", 1000.0);
+        assert!((doubled + 6.02).abs() < 0.05, "gain at cutoff {} dB", doubled);
+        let highpass = gain_db("highpass:freq=1000", 1000.0);
+        assert!((highpass + 3.01).abs() < 0.05, "highpass at cutoff {} dB", highpass);
+    }
+}
@@ -0,0 +1,172 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::DelayLine;
+use crate::parser::parse_duration;
+use crate::sources::{
+    Oscillator,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+pub struct FlangerParams {
+    pub rate: f64,
+    pub depth: f64,
+    pub delay: f64,
+    pub feedback: f64,
+    pub mix: f64,
+}
+
+impl Default for FlangerParams {
+    fn default() -> Self {
+        Self { rate: 0.25, depth: 0.002, delay: 0.001, feedback: 0.5, mix: 0.5 }
+    }
+}
+
+impl FlangerParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "rate" => result.rate = kv[1].parse().map_err(|_| eyre!("Invalid rate value"))?,
+                "depth" => result.depth = parse_duration(kv[1])?,
+                "delay" => result.delay = parse_duration(kv[1])?,
+                "feedback" => {
+                    result.feedback = kv[1].parse().map_err(|_| eyre!("Invalid feedback value"))?
+                }
+                "mix" => result.mix = kv[1].parse().map_err(|_| eyre!("Invalid mix value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.rate <= 0.0 || result.depth < 0.0 || result.delay < 0.0 {
+            bail!("flanger rate must be positive and depth and delay must not be negative");
+        }
+        if result.feedback.abs() >= 1.0 {
+            bail!("flanger feedback must be between -1 and 1 (exclusive)");
+        }
+        if !(0.0..=1.0).contains(&result.mix) {
+            bail!("flanger mix must be between 0 and 1");
+        }
+        Ok(result)
+    }
+}
+
+/// Short swept delay with feedback. The delay moves between `delay` and
+/// `delay + depth` seconds; negative feedback gives the hollow variant.
+pub struct FlangerProcessor {
+    pub rate: f64,
+    pub depth: f64,
+    pub delay: f64,
+    pub feedback: f64,
+    pub mix: f64,
+    sample_rate: f64,
+}
+
+impl FlangerProcessor {
+    #[instrument(level = "debug", fields(rate = %rate, depth = %depth, delay = %delay, feedback = %feedback, mix = %mix))]
+    pub fn new(rate: f64, depth: f64, delay: f64, feedback: f64, mix: f64) -> Self {
+        debug!("Creating flanger at {} Hz with feedback {}", rate, feedback);
+        Self { rate, depth, delay, feedback, mix, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "flanger" {
+            bail!("Not a flanger spec");
+        }
+        let params = FlangerParams::parse(&parts[1..])?;
+        info!("Flanger created at {} Hz", params.rate);
+        Ok(Self::new(params.rate, params.depth, params.delay, params.feedback, params.mix))
+    }
+}
+
+impl Processor for FlangerProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying flanger to {} samples", samples.len());
+        let shortest = (self.delay * self.sample_rate).max(1.0);
+        let sweep = self.depth * self.sample_rate;
+        let mut line = DelayLine::new((shortest + sweep).ceil() as usize + 1);
+        // Start at the shortest delay, where the comb is least audible
+        let mut lfo = Oscillator::with_phase(Waveform::Triangle, 0.75);
+        let increment = self.rate / self.sample_rate;
+
+        for sample in samples.iter_mut() {
+            let dry = *sample;
+            let position = (1.0 + lfo.next_sample(increment)) / 2.0;
+            let wet = line.read(shortest + sweep * position);
+            line.write(dry + self.feedback * wet);
+            *sample = (1.0 - self.mix) * dry + self.mix * wet;
+        }
+        debug!("Flanger processing complete");
+    }
+}
+
+impl Component for FlangerProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), rate = %self.rate))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through flanger", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "flanger:rate={}:depth={}:delay={}:feedback={}:mix={}",
+            self.rate, self.depth, self.delay, self.feedback, self.mix
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn run(spec: &str, buffer: &mut Vec<f64>) {
+        let mut flanger = FlangerProcessor::from_spec(spec).unwrap();
+        Component::process(&mut flanger, buffer, 0.1, 10000.0).unwrap();
+    }
+
+    #[test]
+    fn dry_mix_passes_input_through() {
+        let mut buffer: Vec<f64> = (0..1000).map(|n| (n as f64 * 0.1).sin()).collect();
+        let dry = buffer.clone();
+        run("flanger:mix=0", &mut buffer);
+        assert_eq!(buffer, dry);
+    }
+
+    #[test]
+    fn feedback_recirculates_through_shortest_delay() {
+        let mut buffer = vec![0.0; 100];
+        buffer[0] = 1.0;
+        run("flanger:delay=1ms:depth=0:feedback=-0.5:mix=1", &mut buffer);
+        for (repeat, expected) in [(1, 1.0), (2, -0.5), (3, 0.25)] {
+            assert!((buffer[repeat * 10] - expected).abs() < 1e-12, "repeat {}", repeat);
+        }
+    }
+}
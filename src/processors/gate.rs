@@ -0,0 +1,282 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::compressor::{
+    level_db,
+    smoothing_coefficient,
+};
+use crate::parser::{
+    parse_decibels,
+    parse_duration,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+/// Release time of the level detector that drives the gate, in seconds.
+const DETECTOR_RELEASE: f64 = 0.01;
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GateMode {
+    /// Fully open or closed down to `range`.
+    Gate,
+    /// Downward expansion below the threshold by `ratio`, floored at `range`.
+    Expander { ratio: f64 },
+}
+
+pub struct GateParams {
+    pub threshold: f64,
+    pub hysteresis: f64,
+    pub attack: f64,
+    pub hold: f64,
+    pub release: f64,
+    pub range: f64,
+    pub mode: GateMode,
+}
+
+impl Default for GateParams {
+    fn default() -> Self {
+        Self {
+            threshold: -40.0,
+            hysteresis: 3.0,
+            attack: 0.001,
+            hold: 0.05,
+            release: 0.1,
+            range: -80.0,
+            mode: GateMode::Gate,
+        }
+    }
+}
+
+impl GateParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        let mut mode = "gate".to_string();
+        let mut ratio = 2.0;
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "threshold" => result.threshold = parse_decibels(kv[1])?,
+                "hysteresis" => result.hysteresis = parse_decibels(kv[1])?,
+                "attack" => result.attack = parse_duration(kv[1])?,
+                "hold" => result.hold = parse_duration(kv[1])?,
+                "release" => result.release = parse_duration(kv[1])?,
+                "range" => result.range = parse_decibels(kv[1])?,
+                "mode" => mode = kv[1].to_string(),
+                "ratio" => ratio = kv[1].parse().map_err(|_| eyre!("Invalid ratio value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        result.mode = match mode.as_str() {
+            "gate" => GateMode::Gate,
+            "expander" => GateMode::Expander { ratio },
+            _ => bail!("Unknown gate mode: {} (expected gate or expander)", mode),
+        };
+        if ratio < 1.0 {
+            bail!("gate ratio must be at least 1");
+        }
+        if result.range > 0.0 || result.hysteresis < 0.0 {
+            bail!("gate range must not be positive and hysteresis must not be negative");
+        }
+        if result.attack < 0.0 || result.hold < 0.0 || result.release < 0.0 {
+            bail!("gate attack, hold and release must not be negative");
+        }
+        Ok(result)
+    }
+}
+
+/// Noise gate with hysteresis and hold, or a downward expander. The gate
+/// opens when the detected level rises above `threshold` and only closes
+/// once it has stayed below `threshold - hysteresis` for `hold` seconds.
+pub struct GateProcessor {
+    pub threshold: f64,
+    pub hysteresis: f64,
+    pub attack: f64,
+    pub hold: f64,
+    pub release: f64,
+    pub range: f64,
+    pub mode: GateMode,
+    sample_rate: f64,
+    gain_reduction: Vec<f64>,
+}
+
+impl GateProcessor {
+    #[instrument(level = "debug", fields(threshold = %threshold, mode = ?mode))]
+    pub fn new(
+        threshold: f64,
+        hysteresis: f64,
+        attack: f64,
+        hold: f64,
+        release: f64,
+        range: f64,
+        mode: GateMode,
+    ) -> Self {
+        debug!("Creating {:?} at {} dB with range {} dB", mode, threshold, range);
+        Self {
+            threshold,
+            hysteresis,
+            attack,
+            hold,
+            release,
+            range,
+            mode,
+            sample_rate: 44100.0,
+            gain_reduction: Vec::new(),
+        }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "gate" {
+            bail!("Not a gate spec");
+        }
+        let params = GateParams::parse(&parts[1..])?;
+        info!("Gate created at {} dB", params.threshold);
+        Ok(Self::new(
+            params.threshold,
+            params.hysteresis,
+            params.attack,
+            params.hold,
+            params.release,
+            params.range,
+            params.mode,
+        ))
+    }
+
+    /// Gain change in dB applied to each sample of `samples`, zero where the
+    /// gate is open and down to `range` where it is closed.
+    pub fn gain_curve(&self, samples: &[f64], sample_rate: f64) -> Vec<f64> {
+        let detector = smoothing_coefficient(DETECTOR_RELEASE, sample_rate);
+        let attack = smoothing_coefficient(self.attack, sample_rate);
+        let release = smoothing_coefficient(self.release, sample_rate);
+        let hold_samples = (self.hold * sample_rate).round() as usize;
+        let close_threshold = self.threshold - self.hysteresis;
+
+        let mut envelope: f64 = 0.0;
+        let mut open = false;
+        let mut below_for = 0;
+        let mut gain = self.range;
+        samples
+            .iter()
+            .map(|sample| {
+                envelope = sample.abs().max(detector * envelope);
+                let level = level_db(envelope);
+
+                // Hysteresis and hold decide when the gate may close
+                if level >= self.threshold {
+                    open = true;
+                    below_for = 0;
+                } else if open && level < close_threshold {
+                    below_for += 1;
+                    if below_for > hold_samples {
+                        open = false;
+                    }
+                }
+
+                let target = match self.mode {
+                    GateMode::Gate if open => 0.0,
+                    GateMode::Gate => self.range,
+                    GateMode::Expander { .. } if open => 0.0,
+                    GateMode::Expander { ratio } => {
+                        ((level - self.threshold) * (ratio - 1.0)).clamp(self.range, 0.0)
+                    }
+                };
+                let coefficient = if target > gain { attack } else { release };
+                gain = coefficient * gain + (1.0 - coefficient) * target;
+                gain
+            })
+            .collect()
+    }
+}
+
+impl Processor for GateProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Gating {} samples", samples.len());
+        self.gain_reduction = self.gain_curve(samples, self.sample_rate);
+        for (sample, gain) in samples.iter_mut().zip(&self.gain_reduction) {
+            *sample *= 10f64.powf(gain / 20.0);
+        }
+        let closed = self.gain_reduction.iter().filter(|&&g| g < -1.0).count();
+        let deepest = self.gain_reduction.iter().copied().fold(0.0, f64::min);
+        debug!(
+            "Gate attenuated {:.1}% of samples by more than 1 dB, deepest {:.1} dB",
+            100.0 * closed as f64 / samples.len() as f64,
+            deepest
+        );
+    }
+}
+
+impl Component for GateProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), threshold = %self.threshold))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through gate", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    /// Gain reduction in dB from the most recent run, one value per sample.
+    fn gain_reduction(&self) -> Option<&[f64]> {
+        Some(&self.gain_reduction)
+    }
+
+    fn name(&self) -> String {
+        let mode = match self.mode {
+            GateMode::Gate => "gate".to_string(),
+            GateMode::Expander { ratio } => format!("expander:ratio={}", ratio),
+        };
+        format!(
+            "gate:threshold={}:hysteresis={}:attack={}:hold={}:release={}:range={}:mode={}",
+            self.threshold, self.hysteresis, self.attack, self.hold, self.release, self.range, mode
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::factory::create_component;
+    use crate::pipeline::Pipeline;
+
+    #[test]
+    fn gain_reduction_is_readable_through_pipeline() {
+        let sample_rate = 8000.0;
+        let mut pipeline = Pipeline::new();
+        pipeline
+            .add_component(create_component("seq:notes=[A4/0.2,R/0.4,A4/0.2]").unwrap())
+            .unwrap();
+        pipeline.add_component(create_component("gate:threshold=-30:range=-60").unwrap()).unwrap();
+        let samples = pipeline.run(0.8, sample_rate).unwrap();
+
+        let curve = pipeline.components()[1].gain_reduction().unwrap();
+        assert_eq!(curve.len(), samples.len());
+        let at = |seconds: f64| curve[(seconds * sample_rate) as usize];
+        assert!(at(0.1) > -0.1, "open during the first note: {}", at(0.1));
+        assert!(at(0.55) < -55.0, "closed late in the rest: {}", at(0.55));
+        assert!(at(0.7) > -0.1, "open again during the second note: {}", at(0.7));
+        assert!(curve.iter().all(|&g| (-60.0..=0.0).contains(&g)));
+
+        assert!(pipeline.components()[0].gain_reduction().is_none());
+    }
+}
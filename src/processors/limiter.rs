@@ -0,0 +1,229 @@
+use std::collections::VecDeque;
+
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::compressor::smoothing_coefficient;
+use crate::parser::{
+    parse_decibels,
+    parse_duration,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+pub struct LimiterParams {
+    pub ceiling: f64,
+    pub lookahead: f64,
+    pub release: f64,
+}
+
+impl Default for LimiterParams {
+    fn default() -> Self {
+        Self { ceiling: -1.0, lookahead: 0.005, release: 0.05 }
+    }
+}
+
+impl LimiterParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "ceiling" => result.ceiling = parse_decibels(kv[1])?,
+                "lookahead" => result.lookahead = parse_duration(kv[1])?,
+                "release" => result.release = parse_duration(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.ceiling > 0.0 {
+            bail!("limiter ceiling must be at or below 0 dBFS");
+        }
+        if result.lookahead < 0.0 || result.release < 0.0 {
+            bail!("limiter lookahead and release must not be negative");
+        }
+        Ok(result)
+    }
+}
+
+/// Brickwall limiter. Because the whole buffer is available, the gain looks
+/// `lookahead` seconds ahead without delaying the signal: it starts ramping
+/// down before each peak so that no sample ends up above `ceiling`.
+pub struct LimiterProcessor {
+    pub ceiling: f64,
+    pub lookahead: f64,
+    pub release: f64,
+    sample_rate: f64,
+    gain_reduction: Vec<f64>,
+}
+
+impl LimiterProcessor {
+    #[instrument(level = "debug", fields(ceiling = %ceiling, lookahead = %lookahead, release = %release))]
+    pub fn new(ceiling: f64, lookahead: f64, release: f64) -> Self {
+        debug!("Creating limiter with ceiling {} dB", ceiling);
+        Self { ceiling, lookahead, release, sample_rate: 44100.0, gain_reduction: Vec::new() }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "limiter" {
+            bail!("Not a limiter spec");
+        }
+        let params = LimiterParams::parse(&parts[1..])?;
+        info!("Limiter created with ceiling {} dB", params.ceiling);
+        Ok(Self::new(params.ceiling, params.lookahead, params.release))
+    }
+}
+
+/// Minimum of `values[i..=i + window]` for every `i`, via a deque whose
+/// values increase from front to back.
+fn forward_minimum(values: &[f64], window: usize) -> Vec<f64> {
+    let mut result = vec![0.0; values.len()];
+    let mut candidates: VecDeque<usize> = VecDeque::new();
+    for start in (0..values.len()).rev() {
+        while candidates.back().is_some_and(|&j| values[j] >= values[start]) {
+            candidates.pop_back();
+        }
+        candidates.push_back(start);
+        while candidates.front().is_some_and(|&j| j > start + window) {
+            candidates.pop_front();
+        }
+        result[start] = values[candidates[0]];
+    }
+    result
+}
+
+impl Processor for LimiterProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        let ceiling = 10f64.powf(self.ceiling / 20.0);
+        let window = (self.lookahead * self.sample_rate).round() as usize;
+        debug!(
+            "Limiting {} samples to {:.4} with {} samples lookahead",
+            samples.len(),
+            ceiling,
+            window
+        );
+
+        let required: Vec<f64> = samples
+            .iter()
+            .map(|s| if s.abs() > ceiling { ceiling / s.abs() } else { 1.0 })
+            .collect();
+        let held = forward_minimum(&required, window);
+
+        // Recover towards unity after each peak, never above the held gain
+        let release = smoothing_coefficient(self.release, self.sample_rate);
+        let mut released = Vec::with_capacity(held.len());
+        let mut gain: f64 = 1.0;
+        for &target in &held {
+            gain = target.min(release * gain + (1.0 - release));
+            released.push(gain);
+        }
+
+        // A moving average over the lookahead turns the steps into ramps that
+        // still reach each peak's gain in time
+        let mut sum = 0.0;
+        let mut limited = 0;
+        self.gain_reduction.clear();
+        for i in 0..samples.len() {
+            sum += released[i];
+            if i > window {
+                sum -= released[i - window - 1];
+            }
+            let smoothed = sum / (i.min(window) + 1) as f64;
+            if smoothed < 1.0 {
+                limited += 1;
+            }
+            self.gain_reduction.push(20.0 * smoothed.log10());
+            samples[i] = (samples[i] * smoothed).clamp(-ceiling, ceiling);
+        }
+        debug!("Limiting complete, {} samples attenuated", limited);
+    }
+}
+
+impl Component for LimiterProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), ceiling = %self.ceiling))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through limiter", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    /// Gain reduction in dB from the most recent run, one value per sample.
+    fn gain_reduction(&self) -> Option<&[f64]> {
+        Some(&self.gain_reduction)
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "limiter:ceiling={}:lookahead={}:release={}",
+            self.ceiling, self.lookahead, self.release
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn no_sample_exceeds_ceiling() {
+        let mut buffer: Vec<f64> = (0..44100)
+            .map(|n| {
+                let t = n as f64 / 44100.0;
+                (1.0 + 2.0 * t) * (2.0 * std::f64::consts::PI * 220.0 * t).sin()
+            })
+            .collect();
+        let mut limiter = LimiterProcessor::from_spec("limiter:ceiling=-3dB").unwrap();
+        Component::process(&mut limiter, &mut buffer, 1.0, 44100.0).unwrap();
+        let ceiling = 10f64.powf(-3.0 / 20.0);
+        let peak = buffer.iter().fold(0.0_f64, |m, s| m.max(s.abs()));
+        assert!(peak <= ceiling + 1e-12, "peak {}", peak);
+        assert!(peak > 0.99 * ceiling);
+    }
+
+    #[test]
+    fn quiet_signals_pass_untouched() {
+        let mut buffer: Vec<f64> = (0..4410).map(|n| 0.5 * (n as f64 * 0.03).sin()).collect();
+        let dry = buffer.clone();
+        let mut limiter = LimiterProcessor::from_spec("limiter").unwrap();
+        Component::process(&mut limiter, &mut buffer, 0.1, 44100.0).unwrap();
+        assert_eq!(buffer, dry);
+    }
+
+    #[test]
+    fn gain_ramps_down_before_a_peak() {
+        let mut buffer = vec![0.5; 1000];
+        buffer[500] = 2.0;
+        let mut limiter = LimiterProcessor::from_spec("limiter:ceiling=0:lookahead=10ms").unwrap();
+        Component::process(&mut limiter, &mut buffer, 1.0, 10000.0).unwrap();
+        assert!((buffer[500] - 1.0).abs() < 1e-12);
+        // Attenuation ramps in over the 100 samples of lookahead
+        assert_eq!(buffer[399], 0.5);
+        assert!(buffer[450] < 0.4 && buffer[450] > 0.35, "{}", buffer[450]);
+
+        let curve = Component::gain_reduction(&limiter).unwrap();
+        assert_eq!(curve.len(), 1000);
+        assert_eq!(curve[399], 0.0);
+        assert!((curve[500] + 20.0 * 2f64.log10()).abs() < 1e-9);
+    }
+}
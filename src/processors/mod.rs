@@ -1,5 +1,101 @@
+mod chorus;
+mod compressor;
+mod crush;
+mod curve;
+mod delay;
+mod distort;
+mod envelope;
+mod fade;
+mod filter;
+mod flanger;
+mod gate;
+mod limiter;
+mod normalize;
+mod phaser;
+mod resample;
+mod reverb;
+mod tremolo;
+mod vibrato;
 mod volume;
 
+pub use chorus::{
+    ChorusParams,
+    ChorusProcessor,
+};
+pub use compressor::{
+    CompressorParams,
+    CompressorProcessor,
+};
+pub use crush::{
+    CrushParams,
+    CrushProcessor,
+};
+pub use curve::Curve;
+pub use delay::{
+    DelayLine,
+    DelayParams,
+    DelayProcessor,
+};
+pub use distort::{
+    DistortParams,
+    DistortProcessor,
+    Shape,
+};
+pub use envelope::{
+    EnvelopeParams,
+    EnvelopeProcessor,
+    EnvelopeShape,
+};
+pub use fade::{
+    FadeParams,
+    FadeProcessor,
+};
+pub use filter::{
+    Biquad,
+    FilterParams,
+    FilterProcessor,
+    FilterType,
+};
+pub use flanger::{
+    FlangerParams,
+    FlangerProcessor,
+};
+pub use gate::{
+    GateMode,
+    GateParams,
+    GateProcessor,
+};
+pub use limiter::{
+    LimiterParams,
+    LimiterProcessor,
+};
+pub use normalize::{
+    NormalizeMode,
+    NormalizeParams,
+    NormalizeProcessor,
+};
+pub use phaser::{
+    PhaserParams,
+    PhaserProcessor,
+};
+pub use resample::{
+    ResampleParams,
+    ResampleProcessor,
+    ResampleQuality,
+    resample,
+};
+pub use reverb::{
+    ReverbParams,
+    ReverbProcessor,
+};
+pub use tremolo::{
+    TremoloParams,
+    TremoloProcessor,
+};
+pub use vibrato::{
+    VibratoParams,
+    VibratoProcessor,
+};
 pub use volume::{
     VolumeParams,
     VolumeProcessor,
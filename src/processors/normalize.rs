@@ -0,0 +1,200 @@
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+use tracing::{
+    debug,
+    info,
+    instrument,
+    warn,
+};
+
+use crate::analysers::measure;
+use crate::parser::parse_decibels;
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormalizeMode {
+    Peak,
+    Rms,
+    Lufs,
+}
+
+impl NormalizeMode {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "peak" => Ok(Self::Peak),
+            "rms" => Ok(Self::Rms),
+            "lufs" => Ok(Self::Lufs),
+            _ => bail!("Unknown normalize mode: {} (expected peak, rms or lufs)", name),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Peak => "peak",
+            Self::Rms => "rms",
+            Self::Lufs => "lufs",
+        }
+    }
+
+    /// Target used when none is given: just under full scale for peak, and
+    /// the EBU R128 programme level for loudness.
+    pub fn default_target(&self) -> f64 {
+        match self {
+            Self::Peak => -1.0,
+            Self::Rms => -20.0,
+            Self::Lufs => -23.0,
+        }
+    }
+}
+
+pub struct NormalizeParams {
+    pub mode: NormalizeMode,
+    pub target: f64,
+}
+
+impl Default for NormalizeParams {
+    fn default() -> Self {
+        Self { mode: NormalizeMode::Peak, target: NormalizeMode::Peak.default_target() }
+    }
+}
+
+impl NormalizeParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut mode = NormalizeMode::Peak;
+        let mut target = None;
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "mode" => mode = NormalizeMode::parse(kv[1])?,
+                "target" => target = Some(parse_decibels(kv[1])?),
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        Ok(Self { mode, target: target.unwrap_or_else(|| mode.default_target()) })
+    }
+}
+
+/// Measures the whole buffer and applies the single gain that brings it to
+/// `target` (dBFS for peak and RMS, LUFS for loudness). Measurements come
+/// from `analysers::measure`, the same code the analysers report with.
+pub struct NormalizeProcessor {
+    pub mode: NormalizeMode,
+    pub target: f64,
+    sample_rate: f64,
+}
+
+impl NormalizeProcessor {
+    #[instrument(level = "debug", fields(mode = ?mode, target = %target))]
+    pub fn new(mode: NormalizeMode, target: f64) -> Self {
+        debug!("Creating {} normalizer with target {} dB", mode.name(), target);
+        Self { mode, target, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "normalize" {
+            bail!("Not a normalize spec");
+        }
+        let params = NormalizeParams::parse(&parts[1..])?;
+        info!("Normalizer created: {} to {} dB", params.mode.name(), params.target);
+        Ok(Self::new(params.mode, params.target))
+    }
+
+    /// Current level of `samples` in the units of `target`, or `None` for
+    /// silence.
+    pub fn measure(&self, samples: &[f64]) -> Option<f64> {
+        let decibels = |level: f64| (level > 0.0).then(|| 20.0 * level.log10());
+        match self.mode {
+            NormalizeMode::Peak => decibels(measure::peak(samples)),
+            NormalizeMode::Rms => decibels(measure::rms(samples)),
+            NormalizeMode::Lufs => measure::integrated_loudness(samples, self.sample_rate),
+        }
+    }
+}
+
+impl Processor for NormalizeProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), mode = ?self.mode))]
+    fn process(&mut self, samples: &mut [f64]) {
+        let Some(level) = self.measure(samples) else {
+            warn!("Cannot normalize silence; leaving {} samples unchanged", samples.len());
+            return;
+        };
+        let gain_db = self.target - level;
+        debug!("Measured {:.2} dB {}, applying {:+.2} dB", level, self.mode.name(), gain_db);
+
+        let gain = 10f64.powf(gain_db / 20.0);
+        for sample in samples.iter_mut() {
+            *sample *= gain;
+        }
+        debug!("Normalization complete");
+    }
+}
+
+impl Component for NormalizeProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), mode = ?self.mode))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through normalizer", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!("normalize:mode={}:target={}dB", self.mode.name(), self.target)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn normalized(spec: &str, samples: &[f64]) -> Vec<f64> {
+        let mut buffer = samples.to_vec();
+        let mut normalizer = NormalizeProcessor::from_spec(spec).unwrap();
+        Component::process(&mut normalizer, &mut buffer, 1.0, 48000.0).unwrap();
+        buffer
+    }
+
+    fn reference_sine() -> Vec<f64> {
+        (0..5 * 48000)
+            .map(|n| 0.1 * (2.0 * std::f64::consts::PI * 997.0 * n as f64 / 48000.0).sin())
+            .collect()
+    }
+
+    #[test]
+    fn lufs_target_leaves_reference_level_alone() {
+        let sine = reference_sine();
+        let output = normalized("normalize:mode=lufs:target=-23", &sine);
+        let change_db = 20.0 * (measure::peak(&output) / measure::peak(&sine)).log10();
+        assert!(change_db.abs() < 0.1, "changed by {} dB", change_db);
+    }
+
+    #[test]
+    fn peak_and_rms_reach_target() {
+        let sine = reference_sine();
+        let peak = normalized("normalize:mode=peak:target=-1", &sine);
+        assert!((20.0 * measure::peak(&peak).log10() + 1.0).abs() < 1e-9);
+        let rms = normalized("normalize:mode=rms:target=-10", &sine);
+        assert!((20.0 * measure::rms(&rms).log10() + 10.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn silence_is_left_unchanged() {
+        assert_eq!(normalized("normalize:mode=lufs", &[0.0; 4800]), [0.0; 4800]);
+    }
+}
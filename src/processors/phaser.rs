@@ -0,0 +1,218 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::sources::{
+    Oscillator,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+/// Octaves either side of `freq` that the notches sweep at full depth.
+const SWEEP_OCTAVES: f64 = 4.0;
+
+/// First-order allpass whose break frequency can move every sample.
+#[derive(Debug, Clone, Default)]
+struct AllpassStage {
+    input: f64,
+    output: f64,
+}
+
+impl AllpassStage {
+    fn process(&mut self, x: f64, coefficient: f64) -> f64 {
+        let y = coefficient * x + self.input - coefficient * self.output;
+        self.input = x;
+        self.output = y;
+        y
+    }
+}
+
+pub struct PhaserParams {
+    pub rate: f64,
+    pub depth: f64,
+    pub freq: f64,
+    pub feedback: f64,
+    pub stages: usize,
+    pub mix: f64,
+}
+
+impl Default for PhaserParams {
+    fn default() -> Self {
+        Self { rate: 0.5, depth: 0.5, freq: 1000.0, feedback: 0.3, stages: 4, mix: 0.5 }
+    }
+}
+
+impl PhaserParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "rate" => result.rate = kv[1].parse().map_err(|_| eyre!("Invalid rate value"))?,
+                "depth" => {
+                    result.depth = kv[1].parse().map_err(|_| eyre!("Invalid depth value"))?
+                }
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "feedback" => {
+                    result.feedback = kv[1].parse().map_err(|_| eyre!("Invalid feedback value"))?
+                }
+                "stages" => {
+                    result.stages = kv[1].parse().map_err(|_| eyre!("Invalid stages value"))?
+                }
+                "mix" => result.mix = kv[1].parse().map_err(|_| eyre!("Invalid mix value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.rate <= 0.0 || result.freq <= 0.0 {
+            bail!("phaser rate and freq must be positive");
+        }
+        if !(0.0..=1.0).contains(&result.depth) || !(0.0..=1.0).contains(&result.mix) {
+            bail!("phaser depth and mix must be between 0 and 1");
+        }
+        if result.feedback.abs() >= 1.0 {
+            bail!("phaser feedback must be between -1 and 1 (exclusive)");
+        }
+        if result.stages == 0 || result.stages % 2 != 0 || result.stages > 24 {
+            bail!("phaser stages must be an even number from 2 to 24");
+        }
+        Ok(result)
+    }
+}
+
+/// Chain of `stages` first-order allpasses swept around `freq`, mixed with
+/// the dry signal to create `stages / 2` moving notches. `depth=1` sweeps
+/// four octaves either side of `freq`.
+pub struct PhaserProcessor {
+    pub rate: f64,
+    pub depth: f64,
+    pub freq: f64,
+    pub feedback: f64,
+    pub stages: usize,
+    pub mix: f64,
+    sample_rate: f64,
+}
+
+impl PhaserProcessor {
+    #[instrument(level = "debug", fields(rate = %rate, depth = %depth, freq = %freq, feedback = %feedback, stages = %stages, mix = %mix))]
+    pub fn new(rate: f64, depth: f64, freq: f64, feedback: f64, stages: usize, mix: f64) -> Self {
+        debug!("Creating {}-stage phaser around {} Hz", stages, freq);
+        Self { rate, depth, freq, feedback, stages, mix, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "phaser" {
+            bail!("Not a phaser spec");
+        }
+        let params = PhaserParams::parse(&parts[1..])?;
+        info!("Phaser created with {} stages", params.stages);
+        Ok(Self::new(
+            params.rate,
+            params.depth,
+            params.freq,
+            params.feedback,
+            params.stages,
+            params.mix,
+        ))
+    }
+}
+
+impl Processor for PhaserProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying phaser to {} samples", samples.len());
+        let mut stages = vec![AllpassStage::default(); self.stages];
+        let mut lfo = Oscillator::new(Waveform::Sine);
+        let increment = self.rate / self.sample_rate;
+        let highest = 0.45 * self.sample_rate;
+        let mut last = 0.0;
+
+        for sample in samples.iter_mut() {
+            let dry = *sample;
+            let octaves = SWEEP_OCTAVES * self.depth * lfo.next_sample(increment);
+            let break_frequency = (self.freq * 2f64.powf(octaves)).min(highest);
+            let t = (PI * break_frequency / self.sample_rate).tan();
+            let coefficient = (t - 1.0) / (t + 1.0);
+
+            let wet = stages
+                .iter_mut()
+                .fold(dry + self.feedback * last, |x, stage| stage.process(x, coefficient));
+            last = wet;
+            *sample = (1.0 - self.mix) * dry + self.mix * wet;
+        }
+        debug!("Phaser processing complete");
+    }
+}
+
+impl Component for PhaserProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), stages = %self.stages))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through phaser", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "phaser:rate={}:depth={}:freq={}:feedback={}:stages={}:mix={}",
+            self.rate, self.depth, self.freq, self.feedback, self.stages, self.mix
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Settled amplitude of a sine at `freq` after the phaser.
+    fn amplitude(spec: &str, freq: f64) -> f64 {
+        let mut buffer: Vec<f64> =
+            (0..44100).map(|n| (2.0 * PI * freq * n as f64 / 44100.0).sin()).collect();
+        let mut phaser = PhaserProcessor::from_spec(spec).unwrap();
+        Component::process(&mut phaser, &mut buffer, 1.0, 44100.0).unwrap();
+        buffer[22050..].iter().fold(0.0_f64, |m, s| m.max(s.abs()))
+    }
+
+    #[test]
+    fn still_phaser_notches_at_freq() {
+        // Two stages turn the phase through 180 degrees at their break frequency
+        let spec = "phaser:depth=0:freq=1000:stages=2:feedback=0:mix=0.5";
+        assert!(amplitude(spec, 1000.0) < 0.01);
+        assert!(amplitude(spec, 100.0) > 0.95);
+        assert!(amplitude(spec, 10000.0) > 0.95);
+    }
+
+    #[test]
+    fn dry_mix_passes_input_through() {
+        let mut buffer: Vec<f64> = (0..4410).map(|n| (n as f64 * 0.1).sin()).collect();
+        let dry = buffer.clone();
+        let mut phaser = PhaserProcessor::from_spec("phaser:mix=0").unwrap();
+        Component::process(&mut phaser, &mut buffer, 0.1, 44100.0).unwrap();
+        assert_eq!(buffer, dry);
+    }
+}
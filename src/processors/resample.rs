@@ -0,0 +1,268 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::traits::Component;
+
+/// Kernel table entries per zero crossing; lookups interpolate between them.
+const TABLE_RESOLUTION: usize = 512;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ResampleQuality {
+    Low,
+    Medium,
+    High,
+}
+
+impl ResampleQuality {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "low" => Ok(Self::Low),
+            "medium" => Ok(Self::Medium),
+            "high" => Ok(Self::High),
+            _ => bail!("Unknown resample quality: {} (expected low, medium or high)", name),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Low => "low",
+            Self::Medium => "medium",
+            Self::High => "high",
+        }
+    }
+
+    /// Zero crossings of the sinc on each side, Kaiser window beta, and
+    /// passband edge as a fraction of the lower Nyquist frequency.
+    fn design(&self) -> (usize, f64, f64) {
+        match self {
+            Self::Low => (8, 6.0, 0.85),
+            Self::Medium => (16, 8.0, 0.9),
+            Self::High => (32, 10.0, 0.95),
+        }
+    }
+}
+
+/// Zeroth-order modified Bessel function of the first kind, by its power
+/// series.
+fn bessel_i0(x: f64) -> f64 {
+    let mut sum = 1.0;
+    let mut term = 1.0;
+    let half_squared = x * x / 4.0;
+    for k in 1..50 {
+        term *= half_squared / (k * k) as f64;
+        sum += term;
+        if term < sum * 1e-16 {
+            break;
+        }
+    }
+    sum
+}
+
+/// Band-limited resampling by a Kaiser-windowed sinc. When converting down,
+/// the kernel is widened so that it also removes everything above the new
+/// Nyquist frequency.
+pub fn resample(
+    samples: &[f64],
+    from_rate: f64,
+    to_rate: f64,
+    quality: ResampleQuality,
+) -> Vec<f64> {
+    if samples.is_empty() || from_rate == to_rate {
+        return samples.to_vec();
+    }
+    let (zero_crossings, beta, rolloff) = quality.design();
+    let ratio = to_rate / from_rate;
+    let scale = ratio.min(1.0) * rolloff;
+
+    // Windowed sinc sampled against u, the distance in zero crossings
+    let window_norm = bessel_i0(beta);
+    let table: Vec<f64> = (0..=zero_crossings * TABLE_RESOLUTION + 1)
+        .map(|i| {
+            let u = i as f64 / TABLE_RESOLUTION as f64;
+            if u >= zero_crossings as f64 {
+                return 0.0;
+            }
+            let sinc = if u == 0.0 { 1.0 } else { (PI * u).sin() / (PI * u) };
+            let r = u / zero_crossings as f64;
+            sinc * bessel_i0(beta * (1.0 - r * r).sqrt()) / window_norm
+        })
+        .collect();
+    let kernel = |distance: f64| {
+        let position = (distance * scale).abs() * TABLE_RESOLUTION as f64;
+        let index = position as usize;
+        if index + 1 >= table.len() {
+            return 0.0;
+        }
+        let frac = position - index as f64;
+        scale * (table[index] + (table[index + 1] - table[index]) * frac)
+    };
+
+    let reach = zero_crossings as f64 / scale;
+    let out_len = (samples.len() as f64 * ratio).round() as usize;
+    (0..out_len)
+        .map(|n| {
+            let centre = n as f64 / ratio;
+            let first = (centre - reach).ceil().max(0.0) as usize;
+            let last = ((centre + reach).floor() as usize).min(samples.len() - 1);
+            (first..=last).map(|k| samples[k] * kernel(centre - k as f64)).sum()
+        })
+        .collect()
+}
+
+pub struct ResampleParams {
+    pub to: f64,
+    pub quality: ResampleQuality,
+}
+
+impl Default for ResampleParams {
+    fn default() -> Self {
+        Self { to: 48000.0, quality: ResampleQuality::High }
+    }
+}
+
+impl ResampleParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "to" => result.to = kv[1].parse().map_err(|_| eyre!("Invalid to value"))?,
+                "quality" => result.quality = ResampleQuality::parse(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.to < 1.0 || result.to.fract() != 0.0 {
+            bail!("resample target rate must be a positive whole number of Hz");
+        }
+        Ok(result)
+    }
+}
+
+/// Converts the buffer to another sample rate. Components after it, and the
+/// written file, run at the new rate. It changes the buffer length, so it is
+/// a `Component` only and not an in-place `Processor`.
+pub struct ResampleProcessor {
+    pub to: f64,
+    pub quality: ResampleQuality,
+}
+
+impl ResampleProcessor {
+    #[instrument(level = "debug", fields(to = %to, quality = ?quality))]
+    pub fn new(to: f64, quality: ResampleQuality) -> Self {
+        debug!("Creating {} quality resampler to {} Hz", quality.name(), to);
+        Self { to, quality }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "resample" {
+            bail!("Not a resample spec");
+        }
+        let params = ResampleParams::parse(&parts[1..])?;
+        info!("Resampler created to {} Hz", params.to);
+        Ok(Self::new(params.to, params.quality))
+    }
+}
+
+impl Component for ResampleProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), to = %self.to))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Resampling {} samples from {} Hz to {} Hz", buffer.len(), sample_rate, self.to);
+        *buffer = resample(buffer, sample_rate, self.to, self.quality);
+        debug!("Resampling complete, {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn output_sample_rate(&self, _sample_rate: f64) -> f64 {
+        self.to
+    }
+
+    fn name(&self) -> String {
+        format!("resample:to={}:quality={}", self.to, self.quality.name())
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factory::create_component;
+    use crate::pipeline::Pipeline;
+
+    fn sine(freq: f64, duration: f64, sample_rate: f64) -> Vec<f64> {
+        (0..(duration * sample_rate) as usize)
+            .map(|n| (2.0 * PI * freq * n as f64 / sample_rate).sin())
+            .collect()
+    }
+
+    /// RMS away from the edges, where the kernel runs off the buffer.
+    fn settled_rms(samples: &[f64]) -> f64 {
+        let settled = &samples[samples.len() / 4..samples.len() * 3 / 4];
+        (settled.iter().map(|s| s * s).sum::<f64>() / settled.len() as f64).sqrt()
+    }
+
+    #[test]
+    fn output_length_follows_ratio() {
+        let input = sine(1000.0, 1.0, 48000.0);
+        assert_eq!(resample(&input, 48000.0, 44100.0, ResampleQuality::Low).len(), 44100);
+        assert_eq!(resample(&input, 48000.0, 96000.0, ResampleQuality::Low).len(), 96000);
+        assert_eq!(resample(&input, 48000.0, 48000.0, ResampleQuality::High), input);
+    }
+
+    #[test]
+    fn passband_keeps_level_and_pitch() {
+        for quality in [ResampleQuality::Low, ResampleQuality::Medium, ResampleQuality::High] {
+            let output = resample(&sine(1000.0, 1.0, 48000.0), 48000.0, 44100.0, quality);
+            let gain_db = 20.0 * (settled_rms(&output) * 2f64.sqrt()).log10();
+            assert!(gain_db.abs() < 0.01, "{} quality gain {} dB", quality.name(), gain_db);
+            let crossings = output.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+            assert!((999..=1000).contains(&crossings), "{} crossings", crossings);
+        }
+    }
+
+    #[test]
+    fn downsampling_rejects_content_above_new_nyquist() {
+        let output =
+            resample(&sine(30000.0, 0.5, 96000.0), 96000.0, 44100.0, ResampleQuality::High);
+        assert!(settled_rms(&output) < 1e-3, "rms {}", settled_rms(&output));
+    }
+
+    #[test]
+    fn pipeline_runs_later_components_at_new_rate() {
+        let mut pipeline = Pipeline::new();
+        for spec in ["sine:freq=440", "resample:to=22050", "lowpass:freq=8000"] {
+            pipeline.add_component(create_component(spec).unwrap()).unwrap();
+        }
+        assert_eq!(pipeline.output_sample_rate(44100.0), 22050.0);
+        assert_eq!(pipeline.run(1.0, 44100.0).unwrap().len(), 22050);
+
+        // A 15 kHz lowpass is fine at 44.1 kHz but above the new Nyquist
+        let mut pipeline = Pipeline::new();
+        for spec in ["sine:freq=440", "resample:to=22050", "lowpass:freq=15000"] {
+            pipeline.add_component(create_component(spec).unwrap()).unwrap();
+        }
+        let error = pipeline.run(1.0, 44100.0).err().unwrap();
+        assert!(format!("{:?}", error).contains("11025"), "{:?}", error);
+    }
+}
@@ -0,0 +1,287 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+/// Freeverb's tunings, in samples at 44.1 kHz.
+const COMB_TUNINGS: [usize; 8] = [1116, 1188, 1277, 1356, 1422, 1491, 1557, 1617];
+const ALLPASS_TUNINGS: [usize; 4] = [556, 441, 341, 225];
+/// Extra length of the right-hand tank, which decorrelates it from the left.
+const STEREO_SPREAD: usize = 23;
+const TUNING_RATE: f64 = 44100.0;
+
+const FIXED_GAIN: f64 = 0.015;
+const SCALE_WET: f64 = 3.0;
+const SCALE_ROOM: f64 = 0.28;
+const OFFSET_ROOM: f64 = 0.7;
+const SCALE_DAMP: f64 = 0.4;
+const ALLPASS_FEEDBACK: f64 = 0.5;
+/// Longest tail a reverb will append to the buffer, in seconds.
+const MAX_TAIL: f64 = 30.0;
+
+/// Lowpass-feedback comb filter.
+struct Comb {
+    buffer: Vec<f64>,
+    index: usize,
+    filter_store: f64,
+}
+
+impl Comb {
+    fn new(length: usize) -> Self {
+        Self { buffer: vec![0.0; length.max(1)], index: 0, filter_store: 0.0 }
+    }
+
+    fn process(&mut self, input: f64, feedback: f64, damp: f64) -> f64 {
+        let output = self.buffer[self.index];
+        self.filter_store = output * (1.0 - damp) + self.filter_store * damp;
+        self.buffer[self.index] = input + self.filter_store * feedback;
+        self.index = (self.index + 1) % self.buffer.len();
+        output
+    }
+}
+
+/// Schroeder allpass diffuser.
+struct Allpass {
+    buffer: Vec<f64>,
+    index: usize,
+}
+
+impl Allpass {
+    fn new(length: usize) -> Self {
+        Self { buffer: vec![0.0; length.max(1)], index: 0 }
+    }
+
+    fn process(&mut self, input: f64) -> f64 {
+        let delayed = self.buffer[self.index];
+        self.buffer[self.index] = input + delayed * ALLPASS_FEEDBACK;
+        self.index = (self.index + 1) % self.buffer.len();
+        delayed - input
+    }
+}
+
+/// One channel of the reverb: parallel combs into series allpasses.
+struct Tank {
+    combs: Vec<Comb>,
+    allpasses: Vec<Allpass>,
+}
+
+impl Tank {
+    fn new(sample_rate: f64, spread: usize) -> Self {
+        let scale = |length: usize| ((length + spread) as f64 * sample_rate / TUNING_RATE) as usize;
+        Self {
+            combs: COMB_TUNINGS.iter().map(|&l| Comb::new(scale(l))).collect(),
+            allpasses: ALLPASS_TUNINGS.iter().map(|&l| Allpass::new(scale(l))).collect(),
+        }
+    }
+
+    fn process(&mut self, input: f64, feedback: f64, damp: f64) -> f64 {
+        let combed: f64 = self.combs.iter_mut().map(|c| c.process(input, feedback, damp)).sum();
+        self.allpasses.iter_mut().fold(combed, |x, allpass| allpass.process(x))
+    }
+}
+
+pub struct ReverbParams {
+    pub room: f64,
+    pub damp: f64,
+    pub width: f64,
+    pub mix: f64,
+    pub tail: bool,
+}
+
+impl Default for ReverbParams {
+    fn default() -> Self {
+        Self { room: 0.5, damp: 0.5, width: 1.0, mix: 0.3, tail: false }
+    }
+}
+
+impl ReverbParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "room" => result.room = kv[1].parse().map_err(|_| eyre!("Invalid room value"))?,
+                "damp" => result.damp = kv[1].parse().map_err(|_| eyre!("Invalid damp value"))?,
+                "width" => {
+                    result.width = kv[1].parse().map_err(|_| eyre!("Invalid width value"))?
+                }
+                "mix" => result.mix = kv[1].parse().map_err(|_| eyre!("Invalid mix value"))?,
+                "tail" => result.tail = kv[1].parse().map_err(|_| eyre!("Invalid tail value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        for (name, value) in [
+            ("room", result.room),
+            ("damp", result.damp),
+            ("width", result.width),
+            ("mix", result.mix),
+        ] {
+            if !(0.0..=1.0).contains(&value) {
+                bail!("reverb {} must be between 0 and 1", name);
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Freeverb: eight damped combs and four allpasses per channel. The output
+/// is the left channel of the stereo algorithm, so `width` blends in the
+/// detuned right-hand tank. There is no randomness, so renders are
+/// byte-stable.
+pub struct ReverbProcessor {
+    pub room: f64,
+    pub damp: f64,
+    pub width: f64,
+    pub mix: f64,
+    pub tail: bool,
+    sample_rate: f64,
+}
+
+impl ReverbProcessor {
+    #[instrument(level = "debug", fields(room = %room, damp = %damp, width = %width, mix = %mix, tail = %tail))]
+    pub fn new(room: f64, damp: f64, width: f64, mix: f64, tail: bool) -> Self {
+        debug!("Creating reverb with room size {} and damping {}", room, damp);
+        Self { room, damp, width, mix, tail, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "reverb" {
+            bail!("Not a reverb spec");
+        }
+        let params = ReverbParams::parse(&parts[1..])?;
+        info!("Reverb processor created with room size {}", params.room);
+        Ok(Self::new(params.room, params.damp, params.width, params.mix, params.tail))
+    }
+
+    fn feedback(&self) -> f64 {
+        self.room * SCALE_ROOM + OFFSET_ROOM
+    }
+
+    /// Time in seconds for the longest comb to decay by 60 dB.
+    pub fn tail_length(&self) -> f64 {
+        let longest = (COMB_TUNINGS[7] + STEREO_SPREAD) as f64 / TUNING_RATE;
+        (-3.0 * longest / self.feedback().log10()).min(MAX_TAIL)
+    }
+}
+
+impl Processor for ReverbProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying reverb to {} samples", samples.len());
+        let feedback = self.feedback();
+        let damp = self.damp * SCALE_DAMP;
+        let wet = self.mix * SCALE_WET;
+        let wet_left = wet * (self.width / 2.0 + 0.5);
+        let wet_right = wet * (1.0 - self.width) / 2.0;
+
+        let mut left = Tank::new(self.sample_rate, 0);
+        let mut right = Tank::new(self.sample_rate, STEREO_SPREAD);
+        for sample in samples.iter_mut() {
+            let input = *sample * FIXED_GAIN;
+            let out_left = left.process(input, feedback, damp);
+            let out_right = right.process(input, feedback, damp);
+            *sample = (1.0 - self.mix) * *sample + wet_left * out_left + wet_right * out_right;
+        }
+        debug!("Reverb processing complete");
+    }
+}
+
+impl Component for ReverbProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), room = %self.room))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        self.sample_rate = sample_rate;
+        if self.tail {
+            let extra = (self.tail_length() * sample_rate).ceil() as usize;
+            debug!("Extending buffer by {} samples for the reverb tail", extra);
+            buffer.resize(buffer.len() + extra, 0.0);
+        }
+        debug!("Processing {} samples through reverb", buffer.len());
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "reverb:room={}:damp={}:width={}:mix={}:tail={}",
+            self.room, self.damp, self.width, self.mix, self.tail
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn impulse_response(spec: &str) -> Vec<f64> {
+        let mut buffer = vec![0.0; 4410];
+        buffer[0] = 1.0;
+        let mut reverb = ReverbProcessor::from_spec(spec).unwrap();
+        Component::process(&mut reverb, &mut buffer, 0.1, 44100.0).unwrap();
+        buffer
+    }
+
+    fn energy(samples: &[f64]) -> f64 {
+        samples.iter().map(|s| s * s).sum()
+    }
+
+    #[test]
+    fn dry_mix_passes_input_through() {
+        let mut buffer: Vec<f64> = (0..4410).map(|n| (n as f64 * 0.05).sin()).collect();
+        let dry = buffer.clone();
+        let mut reverb = ReverbProcessor::from_spec("reverb:mix=0").unwrap();
+        Component::process(&mut reverb, &mut buffer, 0.1, 44100.0).unwrap();
+        assert_eq!(buffer, dry);
+    }
+
+    #[test]
+    fn tail_decays_and_renders_repeatably() {
+        let response = impulse_response("reverb:room=0.5:mix=1:tail=true");
+        assert_eq!(response, impulse_response("reverb:room=0.5:mix=1:tail=true"));
+        let tail = ReverbProcessor::from_spec("reverb:room=0.5").unwrap().tail_length();
+        assert_eq!(response.len(), 4410 + (tail * 44100.0).ceil() as usize);
+
+        let window = 4410;
+        let early = energy(&response[..window]);
+        let late = energy(&response[response.len() - window..]);
+        assert!(early > 0.0);
+        assert!(
+            10.0 * (late / early).log10() < -50.0,
+            "tail ends {} dB down",
+            10.0 * (late / early).log10()
+        );
+    }
+
+    #[test]
+    fn larger_rooms_ring_longer() {
+        let small = ReverbProcessor::from_spec("reverb:room=0.2").unwrap();
+        let large = ReverbProcessor::from_spec("reverb:room=0.9").unwrap();
+        assert!(large.tail_length() > small.tail_length());
+        let small = impulse_response("reverb:room=0.2:mix=1:width=1");
+        let large = impulse_response("reverb:room=0.9:mix=1:width=1");
+        assert!(energy(&large[2205..]) > energy(&small[2205..]));
+    }
+}
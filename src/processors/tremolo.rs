@@ -0,0 +1,124 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::sources::{
+    Oscillator,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+pub struct TremoloParams {
+    pub rate: f64,
+    pub depth: f64,
+    pub shape: Waveform,
+}
+
+impl Default for TremoloParams {
+    fn default() -> Self {
+        Self { rate: 5.0, depth: 0.5, shape: Waveform::Sine }
+    }
+}
+
+impl TremoloParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "rate" => result.rate = kv[1].parse().map_err(|_| eyre!("Invalid rate value"))?,
+                "depth" => {
+                    result.depth = kv[1].parse().map_err(|_| eyre!("Invalid depth value"))?
+                }
+                "shape" => result.shape = Waveform::parse(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.rate <= 0.0 {
+            bail!("tremolo rate must be positive");
+        }
+        if !(0.0..=1.0).contains(&result.depth) {
+            bail!("tremolo depth must be between 0 and 1");
+        }
+        Ok(result)
+    }
+}
+
+/// Amplitude modulation by a low-frequency oscillator. The gain swings
+/// between 1 and `1 - depth`. There is no auto-pan counterpart: the
+/// pipeline renders a single mono channel, so there is nowhere to pan to.
+pub struct TremoloProcessor {
+    pub rate: f64,
+    pub depth: f64,
+    pub shape: Waveform,
+    sample_rate: f64,
+}
+
+impl TremoloProcessor {
+    #[instrument(level = "debug", fields(rate = %rate, depth = %depth, shape = ?shape))]
+    pub fn new(rate: f64, depth: f64, shape: Waveform) -> Self {
+        debug!("Creating {} tremolo at {} Hz, depth {}", shape.name(), rate, depth);
+        Self { rate, depth, shape, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "tremolo" {
+            bail!("Not a tremolo spec");
+        }
+        let params = TremoloParams::parse(&parts[1..])?;
+        info!("Tremolo created at {} Hz", params.rate);
+        Ok(Self::new(params.rate, params.depth, params.shape))
+    }
+}
+
+impl Processor for TremoloProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!("Applying tremolo to {} samples", samples.len());
+        // Start at the top of the cycle so the first sample is untouched
+        let mut lfo = Oscillator::with_phase(self.shape, 0.25);
+        let increment = self.rate / self.sample_rate;
+        for sample in samples.iter_mut() {
+            let lfo_value = lfo.next_sample(increment);
+            *sample *= 1.0 - self.depth * (1.0 - lfo_value) / 2.0;
+        }
+        debug!("Tremolo processing complete");
+    }
+}
+
+impl Component for TremoloProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), rate = %self.rate))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through tremolo", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!("tremolo:rate={}:depth={}:shape={}", self.rate, self.depth, self.shape.name())
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
@@ -0,0 +1,132 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::DelayLine;
+use crate::parser::parse_duration;
+use crate::sources::{
+    Oscillator,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Processor,
+};
+
+pub struct VibratoParams {
+    pub rate: f64,
+    pub depth: f64,
+}
+
+impl Default for VibratoParams {
+    fn default() -> Self {
+        Self { rate: 5.0, depth: 0.002 }
+    }
+}
+
+impl VibratoParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "rate" => result.rate = kv[1].parse().map_err(|_| eyre!("Invalid rate value"))?,
+                "depth" => result.depth = parse_duration(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.rate <= 0.0 || result.depth <= 0.0 {
+            bail!("vibrato rate and depth must be positive");
+        }
+        Ok(result)
+    }
+}
+
+/// Pitch modulation through a delay line whose length sweeps by ±`depth`
+/// seconds at `rate` Hz. The peak pitch deviation is a factor of
+/// `2π · rate · depth`, so the 5 Hz / 2 ms default is about a semitone.
+pub struct VibratoProcessor {
+    pub rate: f64,
+    pub depth: f64,
+    sample_rate: f64,
+}
+
+impl VibratoProcessor {
+    #[instrument(level = "debug", fields(rate = %rate, depth = %depth))]
+    pub fn new(rate: f64, depth: f64) -> Self {
+        debug!("Creating vibrato at {} Hz, depth {} s", rate, depth);
+        Self { rate, depth, sample_rate: 44100.0 }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "vibrato" {
+            bail!("Not a vibrato spec");
+        }
+        let params = VibratoParams::parse(&parts[1..])?;
+        info!("Vibrato created at {} Hz", params.rate);
+        Ok(Self::new(params.rate, params.depth))
+    }
+
+    /// Largest pitch deviation as a frequency ratio above 1.
+    pub fn pitch_deviation(&self) -> f64 {
+        2.0 * PI * self.rate * self.depth
+    }
+}
+
+impl Processor for VibratoProcessor {
+    #[instrument(skip(self, samples), fields(num_samples = %samples.len(), sample_rate = %self.sample_rate))]
+    fn process(&mut self, samples: &mut [f64]) {
+        debug!(
+            "Applying vibrato to {} samples (deviation {:.2}%)",
+            samples.len(),
+            100.0 * self.pitch_deviation()
+        );
+        let swing = self.depth * self.sample_rate;
+        let centre = swing + 1.0;
+        let mut line = DelayLine::new((centre + swing).ceil() as usize + 1);
+        let mut lfo = Oscillator::new(Waveform::Sine);
+        let increment = self.rate / self.sample_rate;
+        for sample in samples.iter_mut() {
+            line.write(*sample);
+            // Reading after writing, a delay of 1 is the current input
+            *sample = line.read(centre + swing * lfo.next_sample(increment));
+        }
+        debug!("Vibrato processing complete");
+    }
+}
+
+impl Component for VibratoProcessor {
+    #[instrument(skip(self, buffer), fields(buffer_len = %buffer.len(), rate = %self.rate))]
+    fn process(&mut self, buffer: &mut Vec<f64>, _duration: f64, sample_rate: f64) -> Result<()> {
+        if buffer.is_empty() {
+            bail!("Processor requires input samples");
+        }
+        debug!("Processing {} samples through vibrato", buffer.len());
+        self.sample_rate = sample_rate;
+        Processor::process(self, buffer);
+        Ok(())
+    }
+
+    fn name(&self) -> String {
+        format!("vibrato:rate={}:depth={}", self.rate, self.depth)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Processor"
+    }
+}
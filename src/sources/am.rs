@@ -0,0 +1,215 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    Oscillator,
+    OscillatorParams,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct AmParams {
+    pub freq: f64,
+    pub mod_freq: f64,
+    pub depth: f64,
+    pub mod_wave: Waveform,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for AmParams {
+    fn default() -> Self {
+        Self {
+            freq: 440.0,
+            mod_freq: 220.0,
+            depth: 0.5,
+            mod_wave: Waveform::Sine,
+            oscillator: OscillatorParams::default(),
+        }
+    }
+}
+
+impl AmParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "mod_freq" => {
+                    result.mod_freq = kv[1].parse().map_err(|_| eyre!("Invalid mod_freq value"))?
+                }
+                "depth" => {
+                    result.depth = kv[1].parse().map_err(|_| eyre!("Invalid depth value"))?
+                }
+                "mod_wave" => result.mod_wave = Waveform::parse(kv[1])?,
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+pub struct AmSource {
+    pub frequency: f64,
+    pub mod_frequency: f64,
+    pub depth: f64,
+    pub mod_waveform: Waveform,
+    pub oscillator: OscillatorParams,
+}
+
+impl AmSource {
+    #[instrument(level = "debug", fields(frequency = %frequency, mod_frequency = %mod_frequency, depth = %depth))]
+    pub fn new(
+        frequency: f64,
+        mod_frequency: f64,
+        depth: f64,
+        mod_waveform: Waveform,
+        oscillator: OscillatorParams,
+    ) -> Self {
+        debug!(
+            "Creating AM source: carrier {} Hz, modulator {} Hz ({}), depth {}",
+            frequency,
+            mod_frequency,
+            mod_waveform.name(),
+            depth
+        );
+        Self { frequency, mod_frequency, depth, mod_waveform, oscillator }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "am" {
+            bail!("Not an am spec");
+        }
+        let params = AmParams::parse(&parts[1..])?;
+        debug!("AM source created at {} Hz from spec", params.freq);
+        Ok(Self::new(
+            params.freq,
+            params.mod_freq,
+            params.depth,
+            params.mod_wave,
+            params.oscillator,
+        ))
+    }
+}
+
+impl Source for AmSource {
+    #[instrument(skip(self), fields(frequency = %self.frequency, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        debug!(
+            "Generating AM wave: {} Hz for {} seconds at {} Hz sample rate",
+            self.frequency, duration, sample_rate
+        );
+        let mut carrier = self.oscillator.oscillator(Waveform::Sine);
+        let mut modulator = Oscillator::new(self.mod_waveform);
+        let normalisation = 1.0 + self.depth.abs();
+
+        self.oscillator.render(duration, sample_rate, |_| {
+            let modulation = modulator.next_sample(self.mod_frequency / sample_rate);
+            carrier.next_sample(self.frequency / sample_rate) * (1.0 + self.depth * modulation)
+                / normalisation
+        })
+    }
+}
+
+impl Component for AmSource {
+    #[instrument(skip(self, buffer), fields(frequency = %self.frequency))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing AM source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "am:freq={}:mod_freq={}:depth={}:mod_wave={}{}",
+            self.frequency,
+            self.mod_frequency,
+            self.depth,
+            self.mod_waveform.name(),
+            self.oscillator
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+/// Sine carrier scaled by `1 + depth * modulator`, normalised so the peak
+/// stays at 1.
+#[instrument(level = "debug", fields(frequency = %frequency, mod_frequency = %mod_frequency, depth = %depth, duration = %duration, sample_rate = %sample_rate))]
+pub fn generate_am_wave(
+    frequency: f64,
+    mod_frequency: f64,
+    depth: f64,
+    mod_waveform: Waveform,
+    duration: f64,
+    sample_rate: f64,
+) -> Vec<f64> {
+    AmSource::new(frequency, mod_frequency, depth, mod_waveform, OscillatorParams::default())
+        .generate(duration, sample_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    /// Amplitude of the component at `frequency` in a 1 s render.
+    fn amplitude(samples: &[f64], frequency: f64, sample_rate: f64) -> f64 {
+        let (mut re, mut im) = (0.0, 0.0);
+        for (n, s) in samples.iter().enumerate() {
+            let phase = 2.0 * PI * frequency * n as f64 / sample_rate;
+            re += s * phase.cos();
+            im += s * phase.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / samples.len() as f64
+    }
+
+    #[test]
+    fn sidebands_scale_with_depth() {
+        let samples = AmSource::from_spec("am:freq=1000:mod_freq=100:depth=0.5")
+            .unwrap()
+            .generate(1.0, 48000.0);
+        // Carrier and sidebands of depth / 2, all scaled by 1 / (1 + depth)
+        for (frequency, expected) in
+            [(1000.0, 1.0 / 1.5), (1100.0, 0.25 / 1.5), (900.0, 0.25 / 1.5)]
+        {
+            let measured = amplitude(&samples, frequency, 48000.0);
+            assert!((measured - expected).abs() < 0.005, "{} Hz: {}", frequency, measured);
+        }
+        let peak = samples.iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+        assert!(peak <= 1.0);
+    }
+}
@@ -0,0 +1,164 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    OscillatorParams,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct BurstParams {
+    pub freq: f64,
+    pub cycles: f64,
+    pub period: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for BurstParams {
+    fn default() -> Self {
+        Self { freq: 1000.0, cycles: 4.0, period: 0.1, oscillator: OscillatorParams::default() }
+    }
+}
+
+impl BurstParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "cycles" => {
+                    result.cycles = kv[1].parse().map_err(|_| eyre!("Invalid cycles value"))?
+                }
+                "period" => {
+                    result.period = kv[1].parse().map_err(|_| eyre!("Invalid period value"))?
+                }
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        if result.freq <= 0.0 || result.cycles <= 0.0 || result.period <= 0.0 {
+            bail!("burst freq, cycles and period must be positive");
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+pub struct BurstSource {
+    pub frequency: f64,
+    pub cycles: f64,
+    pub period: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl BurstSource {
+    #[instrument(level = "debug", fields(frequency = %frequency, cycles = %cycles, period = %period))]
+    pub fn new(frequency: f64, cycles: f64, period: f64, oscillator: OscillatorParams) -> Self {
+        debug!(
+            "Creating tone burst source: {} cycles of {} Hz every {} s",
+            cycles, frequency, period
+        );
+        Self { frequency, cycles, period, oscillator }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "burst" {
+            bail!("Not a burst spec");
+        }
+        let params = BurstParams::parse(&parts[1..])?;
+        debug!("Burst source created at {} Hz from spec", params.freq);
+        Ok(Self::new(params.freq, params.cycles, params.period, params.oscillator))
+    }
+}
+
+impl Source for BurstSource {
+    #[instrument(skip(self), fields(frequency = %self.frequency, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        let burst_samples = (self.cycles / self.frequency * sample_rate).round() as usize;
+        let period_samples = ((self.period * sample_rate).round() as usize).max(1);
+        debug!(
+            "Generating {} samples of {}-sample bursts every {} samples",
+            num_samples, burst_samples, period_samples
+        );
+
+        // Each burst restarts the oscillator at the configured phase
+        let increment = self.frequency / sample_rate;
+        let mut oscillator = self.oscillator.oscillator(Waveform::Sine);
+        self.oscillator.render(duration, sample_rate, |i| {
+            let position = i % period_samples;
+            if position == 0 {
+                oscillator = self.oscillator.oscillator(Waveform::Sine);
+            }
+            if position < burst_samples { oscillator.next_sample(increment) } else { 0.0 }
+        })
+    }
+}
+
+impl Component for BurstSource {
+    #[instrument(skip(self, buffer), fields(frequency = %self.frequency))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing burst source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "burst:freq={}:cycles={}:period={}{}",
+            self.frequency, self.cycles, self.period, self.oscillator
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn bursts_repeat_with_silent_gaps() {
+        let burst = BurstSource::from_spec("burst:freq=1000:cycles=4:period=0.01:amp=0.5").unwrap();
+        let samples = burst.generate(0.05, 48000.0);
+        // 4 cycles of 1 kHz last 192 samples out of every 480
+        for start in (0..samples.len()).step_by(480) {
+            let tone = &samples[start..start + 192];
+            let gap = &samples[start + 192..start + 480];
+            assert!((tone.iter().fold(0.0f64, |p, s| p.max(s.abs())) - 0.5).abs() < 1e-3);
+            assert!(gap.iter().all(|&s| s == 0.0));
+        }
+        assert_eq!(samples[..480], samples[480..960]);
+    }
+}
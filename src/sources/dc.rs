@@ -0,0 +1,119 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct DcParams {
+    pub level: f64,
+}
+
+impl Default for DcParams {
+    fn default() -> Self {
+        Self { level: 1.0 }
+    }
+}
+
+impl DcParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "level" => {
+                    result.level = kv[1].parse().map_err(|_| eyre!("Invalid level value"))?
+                }
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct DcSource {
+    pub level: f64,
+}
+
+impl DcSource {
+    #[instrument(level = "debug", fields(level = %level))]
+    pub fn new(level: f64) -> Self {
+        debug!("Creating DC source at level {}", level);
+        Self { level }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "dc" {
+            bail!("Not a dc spec");
+        }
+        let params = DcParams::parse(&parts[1..])?;
+        debug!("DC source created at level {} from spec", params.level);
+        Ok(Self::new(params.level))
+    }
+}
+
+impl Source for DcSource {
+    #[instrument(skip(self), fields(level = %self.level, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!("Generating {} DC samples at level {}", num_samples, self.level);
+        vec![self.level; num_samples]
+    }
+}
+
+impl Component for DcSource {
+    #[instrument(skip(self, buffer), fields(level = %self.level))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing DC source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("dc:level={}", self.level)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::sources::SilenceSource;
+
+    #[test]
+    fn constant_level_and_silence() {
+        let samples = DcSource::from_spec("dc:level=-0.3").unwrap().generate(0.5, 8000.0);
+        assert_eq!(samples.len(), 4000);
+        assert!(samples.iter().all(|&s| s == -0.3));
+
+        let silence = SilenceSource::from_spec("silence").unwrap().generate(0.5, 8000.0);
+        assert_eq!(silence.len(), 4000);
+        assert!(silence.iter().all(|&s| s == 0.0));
+    }
+}
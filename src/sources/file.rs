@@ -0,0 +1,219 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use crate::audio::read_wav;
+use crate::processors::{
+    ResampleQuality,
+    resample,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ChannelSelect {
+    Mix,
+    Index(usize),
+}
+
+impl ChannelSelect {
+    pub fn parse(value: &str) -> Result<Self> {
+        match value {
+            "mix" => Ok(Self::Mix),
+            _ => value.parse().map(Self::Index).map_err(|_| eyre!("Invalid channel value")),
+        }
+    }
+}
+
+impl std::fmt::Display for ChannelSelect {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Mix => write!(f, "mix"),
+            Self::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+pub struct FileParams {
+    pub path: String,
+    pub channel: ChannelSelect,
+}
+
+impl Default for FileParams {
+    fn default() -> Self {
+        Self { path: String::new(), channel: ChannelSelect::Mix }
+    }
+}
+
+impl FileParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "path" => result.path = kv[1].to_string(),
+                "channel" => result.channel = ChannelSelect::parse(kv[1])?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.path.is_empty() {
+            bail!("file requires a path: file:path=input.wav");
+        }
+        Ok(result)
+    }
+}
+
+pub struct FileSource {
+    pub path: String,
+    pub channel: ChannelSelect,
+    samples: Vec<f64>,
+    file_sample_rate: f64,
+}
+
+impl FileSource {
+    #[instrument(level = "debug", fields(path = %path, channel = %channel))]
+    pub fn new(path: &str, channel: ChannelSelect) -> Result<Self> {
+        let data = read_wav(path).map_err(|e| eyre!("Failed to read {}: {}", path, e))?;
+        let num_channels = data.channels.len();
+
+        let samples = match channel {
+            ChannelSelect::Mix => {
+                let frames = data.channels[0].len();
+                (0..frames)
+                    .map(|i| data.channels.iter().map(|c| c[i]).sum::<f64>() / num_channels as f64)
+                    .collect()
+            }
+            ChannelSelect::Index(index) => match data.channels.into_iter().nth(index) {
+                Some(samples) => samples,
+                None => {
+                    bail!("Channel {} out of range ({} channels in {})", index, num_channels, path)
+                }
+            },
+        };
+
+        info!(
+            "Loaded {} samples at {} Hz from {} (channel {})",
+            samples.len(),
+            data.sample_rate,
+            path,
+            channel
+        );
+        Ok(Self { path: path.to_string(), channel, samples, file_sample_rate: data.sample_rate })
+    }
+
+    /// Length of the decoded file in seconds.
+    pub fn length(&self) -> f64 {
+        self.samples.len() as f64 / self.file_sample_rate
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "file" {
+            bail!("Not a file spec");
+        }
+        let params = FileParams::parse(&parts[1..])?;
+        debug!("File source created for {} from spec", params.path);
+        Self::new(&params.path, params.channel)
+    }
+}
+
+impl Source for FileSource {
+    #[instrument(skip(self), fields(path = %self.path, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!(
+            "Rendering {} samples from {} ({} Hz -> {} Hz)",
+            num_samples, self.path, self.file_sample_rate, sample_rate
+        );
+
+        let mut samples =
+            resample(&self.samples, self.file_sample_rate, sample_rate, ResampleQuality::High);
+        samples.resize(num_samples, 0.0);
+        samples
+    }
+}
+
+impl Component for FileSource {
+    #[instrument(skip(self, buffer), fields(path = %self.path))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing file source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("file:path={}:channel={}", self.path, self.channel)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+    use crate::audio::write_wav;
+
+    /// Writes `seconds` of a sine at `frequency` to a temporary mono WAV and
+    /// loads it back as a source.
+    fn tone_file(name: &str, frequency: f64, seconds: f64, sample_rate: f64) -> FileSource {
+        let samples: Vec<f64> = (0..(seconds * sample_rate) as usize)
+            .map(|n| 0.5 * (2.0 * PI * frequency * n as f64 / sample_rate).sin())
+            .collect();
+        let path = std::env::temp_dir().join(format!("noise-{}-{}.wav", name, std::process::id()));
+        let path = path.to_str().unwrap();
+        write_wav(path, &samples, sample_rate).unwrap();
+        let source = FileSource::new(path, ChannelSelect::Mix).unwrap();
+        std::fs::remove_file(path).unwrap();
+        source
+    }
+
+    #[test]
+    fn resamples_to_pipeline_rate() {
+        let source = tone_file("resample", 1000.0, 0.5, 48000.0);
+        assert_eq!(source.length(), 0.5);
+
+        let samples = source.generate(0.5, 44100.0);
+        assert_eq!(samples.len(), 22050);
+        let rising = samples.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        assert!((499..=500).contains(&rising), "{} cycles in 0.5 s", rising);
+        let peak = samples[2000..20000].iter().fold(0.0f64, |peak, s| peak.max(s.abs()));
+        assert!((peak - 0.5).abs() < 0.01, "peak {}", peak);
+    }
+
+    #[test]
+    fn downsampling_removes_content_above_new_nyquist() {
+        // 20 kHz cannot be represented at 32 kHz and must not fold to 12 kHz
+        let source = tone_file("alias", 20000.0, 0.5, 48000.0);
+        let samples = source.generate(0.5, 32000.0);
+        let settled = &samples[1000..15000];
+        let rms = (settled.iter().map(|s| s * s).sum::<f64>() / settled.len() as f64).sqrt();
+        assert!(rms < 0.005, "rms {} left after downsampling", rms);
+    }
+}
@@ -0,0 +1,219 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    Oscillator,
+    OscillatorParams,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct FmParams {
+    pub freq: f64,
+    pub mod_freq: f64,
+    pub index: f64,
+    pub mod_wave: Waveform,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for FmParams {
+    fn default() -> Self {
+        Self {
+            freq: 440.0,
+            mod_freq: 220.0,
+            index: 1.0,
+            mod_wave: Waveform::Sine,
+            oscillator: OscillatorParams::default(),
+        }
+    }
+}
+
+impl FmParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "mod_freq" => {
+                    result.mod_freq = kv[1].parse().map_err(|_| eyre!("Invalid mod_freq value"))?
+                }
+                "index" => {
+                    result.index = kv[1].parse().map_err(|_| eyre!("Invalid index value"))?
+                }
+                "mod_wave" => result.mod_wave = Waveform::parse(kv[1])?,
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+pub struct FmSource {
+    pub frequency: f64,
+    pub mod_frequency: f64,
+    pub index: f64,
+    pub mod_waveform: Waveform,
+    pub oscillator: OscillatorParams,
+}
+
+impl FmSource {
+    #[instrument(level = "debug", fields(frequency = %frequency, mod_frequency = %mod_frequency, index = %index))]
+    pub fn new(
+        frequency: f64,
+        mod_frequency: f64,
+        index: f64,
+        mod_waveform: Waveform,
+        oscillator: OscillatorParams,
+    ) -> Self {
+        debug!(
+            "Creating FM source: carrier {} Hz, modulator {} Hz ({}), index {}",
+            frequency,
+            mod_frequency,
+            mod_waveform.name(),
+            index
+        );
+        Self { frequency, mod_frequency, index, mod_waveform, oscillator }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "fm" {
+            bail!("Not an fm spec");
+        }
+        let params = FmParams::parse(&parts[1..])?;
+        debug!("FM source created at {} Hz from spec", params.freq);
+        Ok(Self::new(
+            params.freq,
+            params.mod_freq,
+            params.index,
+            params.mod_wave,
+            params.oscillator,
+        ))
+    }
+}
+
+impl Source for FmSource {
+    #[instrument(skip(self), fields(frequency = %self.frequency, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        debug!(
+            "Generating FM wave: {} Hz for {} seconds at {} Hz sample rate",
+            self.frequency, duration, sample_rate
+        );
+        let mut carrier = self.oscillator.oscillator(Waveform::Sine);
+        let mut modulator = Oscillator::new(self.mod_waveform);
+        let deviation = self.index * self.mod_frequency;
+
+        self.oscillator.render(duration, sample_rate, |_| {
+            let modulation = modulator.next_sample(self.mod_frequency / sample_rate);
+            carrier.next_sample((self.frequency + deviation * modulation) / sample_rate)
+        })
+    }
+}
+
+impl Component for FmSource {
+    #[instrument(skip(self, buffer), fields(frequency = %self.frequency))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing FM source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "fm:freq={}:mod_freq={}:index={}:mod_wave={}{}",
+            self.frequency,
+            self.mod_frequency,
+            self.index,
+            self.mod_waveform.name(),
+            self.oscillator
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+/// Sine carrier whose instantaneous frequency is deviated by
+/// `index * mod_frequency` times the modulator output.
+#[instrument(level = "debug", fields(frequency = %frequency, mod_frequency = %mod_frequency, index = %index, duration = %duration, sample_rate = %sample_rate))]
+pub fn generate_fm_wave(
+    frequency: f64,
+    mod_frequency: f64,
+    index: f64,
+    mod_waveform: Waveform,
+    duration: f64,
+    sample_rate: f64,
+) -> Vec<f64> {
+    FmSource::new(frequency, mod_frequency, index, mod_waveform, OscillatorParams::default())
+        .generate(duration, sample_rate)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use super::*;
+
+    /// Amplitude of the component at `frequency` in a 1 s render.
+    fn amplitude(samples: &[f64], frequency: f64, sample_rate: f64) -> f64 {
+        let (mut re, mut im) = (0.0, 0.0);
+        for (n, s) in samples.iter().enumerate() {
+            let phase = 2.0 * PI * frequency * n as f64 / sample_rate;
+            re += s * phase.cos();
+            im += s * phase.sin();
+        }
+        2.0 * (re * re + im * im).sqrt() / samples.len() as f64
+    }
+
+    #[test]
+    fn sidebands_follow_bessel_amplitudes() {
+        let samples = FmSource::from_spec("fm:freq=1000:mod_freq=100:index=2")
+            .unwrap()
+            .generate(1.0, 48000.0);
+        // J0(2) at the carrier and J1(2), J2(2) at the sidebands
+        for (frequency, expected) in
+            [(1000.0, 0.2239), (1100.0, 0.5767), (900.0, 0.5767), (1200.0, 0.3528)]
+        {
+            let measured = amplitude(&samples, frequency, 48000.0);
+            assert!((measured - expected).abs() < 0.01, "{} Hz: {}", frequency, measured);
+        }
+    }
+
+    #[test]
+    fn zero_index_is_a_plain_sine() {
+        let samples = FmSource::from_spec("fm:freq=1000:index=0").unwrap().generate(1.0, 48000.0);
+        assert!((amplitude(&samples, 1000.0, 48000.0) - 1.0).abs() < 1e-6);
+        assert!(amplitude(&samples, 1220.0, 48000.0) < 1e-6);
+    }
+}
@@ -0,0 +1,127 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct ImpulseParams {
+    pub at: f64,
+    pub amp: f64,
+}
+
+impl Default for ImpulseParams {
+    fn default() -> Self {
+        Self { at: 0.0, amp: 1.0 }
+    }
+}
+
+impl ImpulseParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "at" => result.at = kv[1].parse().map_err(|_| eyre!("Invalid at value"))?,
+                "amp" => result.amp = kv[1].parse().map_err(|_| eyre!("Invalid amp value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct ImpulseSource {
+    pub at: f64,
+    pub amp: f64,
+}
+
+impl ImpulseSource {
+    #[instrument(level = "debug", fields(at = %at, amp = %amp))]
+    pub fn new(at: f64, amp: f64) -> Self {
+        debug!("Creating impulse source at {} s with amplitude {}", at, amp);
+        Self { at, amp }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "impulse" {
+            bail!("Not an impulse spec");
+        }
+        let params = ImpulseParams::parse(&parts[1..])?;
+        debug!("Impulse source created at {} s from spec", params.at);
+        Ok(Self::new(params.at, params.amp))
+    }
+}
+
+impl Source for ImpulseSource {
+    #[instrument(skip(self), fields(at = %self.at, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        let index = (self.at * sample_rate).round();
+        debug!("Generating {} samples with an impulse at sample {}", num_samples, index);
+
+        let mut samples = vec![0.0; num_samples];
+        if index >= 0.0 && (index as usize) < num_samples {
+            samples[index as usize] = self.amp;
+        }
+        samples
+    }
+}
+
+impl Component for ImpulseSource {
+    #[instrument(skip(self, buffer), fields(at = %self.at))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing impulse source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("impulse:at={}:amp={}", self.at, self.amp)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn single_sample_at_requested_time() {
+        let samples =
+            ImpulseSource::from_spec("impulse:at=0.01:amp=0.5").unwrap().generate(0.1, 1000.0);
+        assert_eq!(samples.len(), 100);
+        assert_eq!(samples[10], 0.5);
+        assert_eq!(samples.iter().filter(|&&s| s != 0.0).count(), 1);
+
+        // An impulse past the end of the buffer leaves it silent
+        let late = ImpulseSource::from_spec("impulse:at=1").unwrap().generate(0.1, 1000.0);
+        assert!(late.iter().all(|&s| s == 0.0));
+    }
+}
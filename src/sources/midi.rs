@@ -0,0 +1,183 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+};
+
+use super::oscillator::Waveform;
+use super::seq::{
+    midi_to_frequency,
+    render_note,
+};
+use crate::midi::{
+    MidiNote,
+    read_midi,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+/// General MIDI reserves channel 10 (index 9) for percussion.
+const DRUM_CHANNEL: u8 = 9;
+
+pub struct MidiParams {
+    pub path: String,
+    pub wave: Waveform,
+    pub drums: bool,
+    pub amp: f64,
+}
+
+impl Default for MidiParams {
+    fn default() -> Self {
+        Self { path: String::new(), wave: Waveform::Sine, drums: false, amp: 1.0 }
+    }
+}
+
+impl MidiParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "path" => result.path = kv[1].to_string(),
+                "wave" => result.wave = Waveform::parse(kv[1])?,
+                "drums" => {
+                    result.drums = kv[1].parse().map_err(|_| eyre!("Invalid drums value"))?
+                }
+                "amp" => result.amp = kv[1].parse().map_err(|_| eyre!("Invalid amp value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.path.is_empty() {
+            bail!("midi requires a path: midi:path=song.mid");
+        }
+        Ok(result)
+    }
+}
+
+pub struct MidiSource {
+    pub path: String,
+    pub wave: Waveform,
+    pub drums: bool,
+    pub amp: f64,
+    notes: Vec<MidiNote>,
+}
+
+impl MidiSource {
+    #[instrument(level = "debug", fields(path = %path, wave = ?wave, drums = %drums))]
+    pub fn new(path: &str, wave: Waveform, drums: bool, amp: f64) -> Result<Self> {
+        let notes: Vec<MidiNote> = read_midi(path)?
+            .into_iter()
+            .filter(|note| drums || note.channel != DRUM_CHANNEL)
+            .collect();
+        info!("Loaded {} notes from {}", notes.len(), path);
+        Ok(Self { path: path.to_string(), wave, drums, amp, notes })
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "midi" {
+            bail!("Not a midi spec");
+        }
+        let params = MidiParams::parse(&parts[1..])?;
+        debug!("MIDI source created for {} from spec", params.path);
+        Self::new(&params.path, params.wave, params.drums, params.amp)
+    }
+
+    pub fn notes(&self) -> &[MidiNote] {
+        &self.notes
+    }
+
+    /// Largest number of notes sounding at the same time.
+    pub fn max_polyphony(&self) -> usize {
+        let mut edges: Vec<(f64, i32)> =
+            self.notes.iter().flat_map(|n| [(n.start, 1), (n.start + n.duration, -1)]).collect();
+        // Note-offs sort before note-ons at the same instant
+        edges.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.cmp(&b.1)));
+        let mut sounding = 0;
+        let mut max = 0;
+        for (_, change) in edges {
+            sounding += change;
+            max = max.max(sounding);
+        }
+        max as usize
+    }
+}
+
+impl Source for MidiSource {
+    #[instrument(skip(self), fields(path = %self.path, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        // Normalise by peak polyphony, as Parallel does by source count
+        let gain = self.amp / self.max_polyphony().max(1) as f64;
+        debug!(
+            "Rendering {} MIDI notes into {} samples (gain {})",
+            self.notes.len(),
+            num_samples,
+            gain
+        );
+
+        let mut samples = vec![0.0; num_samples];
+        for note in &self.notes {
+            let first = ((note.start * sample_rate).round() as usize).min(num_samples);
+            let last = (((note.start + note.duration) * sample_rate).round() as usize)
+                .clamp(first, num_samples);
+            if first == last {
+                continue;
+            }
+            render_note(
+                &mut samples[first..last],
+                self.wave,
+                midi_to_frequency(f64::from(note.key)),
+                gain * f64::from(note.velocity) / 127.0,
+                sample_rate,
+            );
+        }
+
+        debug!("MIDI rendering complete: {} samples", samples.len());
+        samples
+    }
+}
+
+impl Component for MidiSource {
+    #[instrument(skip(self, buffer), fields(path = %self.path))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing MIDI source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "midi:path={}:wave={}:drums={}:amp={}",
+            self.path,
+            self.wave.name(),
+            self.drums,
+            self.amp
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
@@ -1,13 +1,116 @@
+mod am;
+mod burst;
+mod dc;
+mod file;
+mod fm;
+mod granular;
+mod impulse;
+mod midi;
+mod noise;
+mod oscillator;
+mod pluck;
+mod pulse;
+mod saw;
+mod seq;
+mod silence;
 mod sine;
 mod square;
+mod step;
+mod sweep;
+mod triangle;
+mod wavetable;
 
+pub use am::{
+    AmParams,
+    AmSource,
+    generate_am_wave,
+};
+pub use burst::{
+    BurstParams,
+    BurstSource,
+};
+pub use dc::{
+    DcParams,
+    DcSource,
+};
+pub use file::{
+    ChannelSelect,
+    FileParams,
+    FileSource,
+};
+pub use fm::{
+    FmParams,
+    FmSource,
+    generate_fm_wave,
+};
+pub use granular::{
+    GranularParams,
+    GranularSource,
+};
+pub use impulse::{
+    ImpulseParams,
+    ImpulseSource,
+};
+pub use midi::{
+    MidiParams,
+    MidiSource,
+};
+pub use noise::{
+    NoiseColor,
+    NoiseParams,
+    NoiseSource,
+    generate_noise,
+};
+pub use oscillator::{
+    Oscillator,
+    OscillatorParams,
+    ToneParams,
+    Waveform,
+    generate_waveform,
+};
+pub use pluck::{
+    PluckParams,
+    PluckSource,
+};
+pub use pulse::{
+    PulseParams,
+    PulseWaveSource,
+    generate_pulse_wave,
+};
+pub use saw::{
+    SawWaveSource,
+    generate_saw_wave,
+};
+pub use seq::{
+    Note,
+    SeqParams,
+    SeqSource,
+    midi_to_frequency,
+    parse_note_name,
+};
+pub use silence::SilenceSource;
 pub use sine::{
-    SineParams,
     SineWaveSource,
     generate_sine_wave,
 };
 pub use square::{
-    SquareParams,
     SquareWaveSource,
     generate_square_wave,
 };
+pub use step::{
+    StepParams,
+    StepSource,
+};
+pub use sweep::{
+    SweepMode,
+    SweepParams,
+    SweepSource,
+};
+pub use triangle::{
+    TriangleWaveSource,
+    generate_triangle_wave,
+};
+pub use wavetable::{
+    WavetableParams,
+    WavetableSource,
+};
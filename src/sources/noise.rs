@@ -0,0 +1,333 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use crate::random::Rng;
+use crate::traits::{
+    Component,
+    Source,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoiseColor {
+    White,
+    Pink,
+    Brown,
+    Blue,
+    Violet,
+}
+
+impl NoiseColor {
+    pub fn parse(name: &str) -> Result<Self> {
+        let color = match name {
+            "white" => Self::White,
+            "pink" => Self::Pink,
+            "brown" => Self::Brown,
+            "blue" => Self::Blue,
+            "violet" => Self::Violet,
+            _ => bail!("Unknown noise color: {}", name),
+        };
+        Ok(color)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::White => "white",
+            Self::Pink => "pink",
+            Self::Brown => "brown",
+            Self::Blue => "blue",
+            Self::Violet => "violet",
+        }
+    }
+}
+
+pub struct NoiseParams {
+    pub seed: u64,
+    pub amp: f64,
+}
+
+impl Default for NoiseParams {
+    fn default() -> Self {
+        Self { seed: 0, amp: 1.0 }
+    }
+}
+
+impl NoiseParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "seed" => result.seed = kv[1].parse().map_err(|_| eyre!("Invalid seed value"))?,
+                "amp" => result.amp = kv[1].parse().map_err(|_| eyre!("Invalid amp value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct NoiseSource {
+    pub color: NoiseColor,
+    pub seed: u64,
+    pub amp: f64,
+}
+
+impl NoiseSource {
+    #[instrument(level = "debug", fields(color = ?color, seed = %seed, amp = %amp))]
+    pub fn new(color: NoiseColor, seed: u64, amp: f64) -> Self {
+        debug!("Creating {} noise source with seed {} and amp {}", color.name(), seed, amp);
+        Self { color, seed, amp }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        let color = NoiseColor::parse(parts[0]).map_err(|_| eyre!("Not a noise spec"))?;
+        let params = NoiseParams::parse(&parts[1..])?;
+        debug!("{} noise source created from spec", color.name());
+        Ok(Self::new(color, params.seed, params.amp))
+    }
+}
+
+impl Source for NoiseSource {
+    #[instrument(skip(self), fields(color = ?self.color, seed = %self.seed, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        debug!(
+            "Generating {} noise for {} seconds at {} Hz sample rate",
+            self.color.name(),
+            duration,
+            sample_rate
+        );
+        generate_noise(self.color, self.seed, self.amp, duration, sample_rate)
+    }
+}
+
+impl Component for NoiseSource {
+    #[instrument(skip(self, buffer), fields(color = ?self.color, seed = %self.seed))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing noise source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("{}:seed={}:amp={}", self.color.name(), self.seed, self.amp)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+/// Paul Kellet's refined pink filter: a parallel bank of one-pole lowpasses
+/// approximating a -3 dB/octave slope. The coefficients were fitted at
+/// 44.1 kHz, so poles are moved to keep their corner frequencies (and DC
+/// gain) at other sample rates.
+struct PinkFilter {
+    poles: [f64; 5],
+    gains: [f64; 5],
+    state: [f64; 7],
+}
+
+impl PinkFilter {
+    const REFERENCE_RATE: f64 = 44100.0;
+    const POLES: [f64; 5] = [0.99886, 0.99332, 0.96900, 0.86650, 0.55000];
+    const GAINS: [f64; 5] = [0.0555179, 0.0750759, 0.1538520, 0.3104856, 0.5329522];
+
+    fn new(sample_rate: f64) -> Self {
+        let mut poles = Self::POLES;
+        let mut gains = Self::GAINS;
+        for (pole, gain) in poles.iter_mut().zip(gains.iter_mut()) {
+            let scaled = pole.powf(Self::REFERENCE_RATE / sample_rate);
+            *gain *= (1.0 - scaled) / (1.0 - *pole);
+            *pole = scaled;
+        }
+        Self { poles, gains, state: [0.0; 7] }
+    }
+
+    fn next(&mut self, white: f64) -> f64 {
+        for i in 0..5 {
+            self.state[i] = self.poles[i] * self.state[i] + white * self.gains[i];
+        }
+        self.state[5] = -0.7616 * self.state[5] - white * 0.0168980;
+        let pink = self.state.iter().sum::<f64>() + white * 0.5362;
+        self.state[6] = white * 0.115926;
+        pink * 0.11
+    }
+}
+
+/// Leaky integrator for brown noise. The leak sets a corner below which the
+/// spectrum flattens; it is derived from the sample rate so the corner stays
+/// at `CORNER` Hz and the -6 dB/octave slope covers the audio band at any
+/// rate. The input gain keeps the output RMS independent of the rate.
+struct BrownFilter {
+    leak: f64,
+    gain: f64,
+    state: f64,
+}
+
+impl BrownFilter {
+    const CORNER: f64 = 5.0;
+    const LEVEL: f64 = 0.45;
+
+    fn new(sample_rate: f64) -> Self {
+        let leak = (-2.0 * PI * Self::CORNER / sample_rate).exp();
+        let gain = Self::LEVEL * (1.0 - leak * leak).sqrt();
+        Self { leak, gain, state: 0.0 }
+    }
+
+    fn next(&mut self, white: f64) -> f64 {
+        self.state = self.leak * self.state + self.gain * white;
+        self.state
+    }
+}
+
+#[instrument(level = "debug", fields(color = ?color, seed = %seed, amp = %amp, duration = %duration, sample_rate = %sample_rate))]
+pub fn generate_noise(
+    color: NoiseColor,
+    seed: u64,
+    amp: f64,
+    duration: f64,
+    sample_rate: f64,
+) -> Vec<f64> {
+    let num_samples = (duration * sample_rate) as usize;
+    debug!("Generating {} samples of {} noise", num_samples, color.name());
+
+    let mut rng = Rng::new(seed);
+    let mut pink = PinkFilter::new(sample_rate);
+    let mut brown = BrownFilter::new(sample_rate);
+    let mut previous = 0.0;
+
+    let mut samples = Vec::with_capacity(num_samples);
+    for _ in 0..num_samples {
+        let white = rng.next_bipolar();
+        let sample = match color {
+            NoiseColor::White => white,
+            NoiseColor::Pink => pink.next(white),
+            NoiseColor::Brown => brown.next(white),
+            NoiseColor::Blue => {
+                let current = pink.next(white);
+                let blue = (current - previous) * 2.5;
+                previous = current;
+                blue
+            }
+            NoiseColor::Violet => {
+                let violet = (white - previous) * 0.5;
+                previous = white;
+                violet
+            }
+        };
+        samples.push(sample * amp);
+    }
+
+    debug!("Noise generation complete: {} samples", samples.len());
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SEGMENT: usize = 2048;
+
+    /// Mean Hann-windowed periodogram power per bin between `low` and `high`
+    /// Hz, in dB, averaged over consecutive segments.
+    fn band_density(samples: &[f64], sample_rate: f64, low: f64, high: f64) -> f64 {
+        let window: Vec<f64> = (0..SEGMENT)
+            .map(|n| 0.5 - 0.5 * (2.0 * PI * n as f64 / SEGMENT as f64).cos())
+            .collect();
+        let (cos, sin): (Vec<f64>, Vec<f64>) = (0..SEGMENT)
+            .map(|n| {
+                let phase = 2.0 * PI * n as f64 / SEGMENT as f64;
+                (phase.cos(), phase.sin())
+            })
+            .unzip();
+        let bin_width = sample_rate / SEGMENT as f64;
+        let bins = (low / bin_width).ceil() as usize..(high / bin_width).ceil() as usize;
+
+        let mut total = 0.0;
+        let mut count = 0;
+        for segment in samples.chunks_exact(SEGMENT) {
+            for bin in bins.clone() {
+                let (mut re, mut im) = (0.0, 0.0);
+                for (n, sample) in segment.iter().enumerate() {
+                    let index = (bin * n) % SEGMENT;
+                    re += sample * window[n] * cos[index];
+                    im -= sample * window[n] * sin[index];
+                }
+                total += re * re + im * im;
+                count += 1;
+            }
+        }
+        10.0 * (total / count as f64).log10()
+    }
+
+    /// Least-squares slope of density across the octaves from 125 Hz to
+    /// 8 kHz, in dB per octave.
+    fn octave_slope(color: NoiseColor, sample_rate: f64) -> f64 {
+        let samples = generate_noise(color, 7, 1.0, 1.0, sample_rate);
+        let points: Vec<(f64, f64)> = (0..6)
+            .map(|octave| {
+                let low = 125.0 * 2f64.powi(octave);
+                (octave as f64, band_density(&samples, sample_rate, low, 2.0 * low))
+            })
+            .collect();
+        let n = points.len() as f64;
+        let mean_x = points.iter().map(|p| p.0).sum::<f64>() / n;
+        let mean_y = points.iter().map(|p| p.1).sum::<f64>() / n;
+        let covariance: f64 = points.iter().map(|p| (p.0 - mean_x) * (p.1 - mean_y)).sum();
+        let variance: f64 = points.iter().map(|p| (p.0 - mean_x).powi(2)).sum();
+        covariance / variance
+    }
+
+    #[test]
+    fn spectral_slopes_match_colors() {
+        for sample_rate in [44100.0, 96000.0] {
+            for (color, expected) in
+                [(NoiseColor::White, 0.0), (NoiseColor::Pink, -3.0), (NoiseColor::Brown, -6.0)]
+            {
+                let slope = octave_slope(color, sample_rate);
+                assert!(
+                    (slope - expected).abs() < 0.5,
+                    "{} noise at {} Hz: {:.2} dB/octave, expected {}",
+                    color.name(),
+                    sample_rate,
+                    slope,
+                    expected
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn brown_level_is_independent_of_sample_rate() {
+        let rms = |sample_rate: f64| {
+            let samples = generate_noise(NoiseColor::Brown, 3, 1.0, 20.0, sample_rate);
+            (samples.iter().map(|s| s * s).sum::<f64>() / samples.len() as f64).sqrt()
+        };
+        let (low, high) = (rms(44100.0), rms(96000.0));
+        assert!((low / high - 1.0).abs() < 0.25, "rms {} at 44.1 kHz vs {} at 96 kHz", low, high);
+    }
+}
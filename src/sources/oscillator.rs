@@ -0,0 +1,390 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Waveform {
+    Sine,
+    Square,
+    Saw,
+    Triangle,
+    Pulse(f64),
+}
+
+impl Waveform {
+    pub fn parse(name: &str) -> Result<Self> {
+        let waveform = match name {
+            "sine" => Self::Sine,
+            "square" => Self::Square,
+            "saw" => Self::Saw,
+            "triangle" => Self::Triangle,
+            "pulse" => Self::Pulse(0.5),
+            _ => bail!("Unknown waveform: {}", name),
+        };
+        Ok(waveform)
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Sine => "sine",
+            Self::Square => "square",
+            Self::Saw => "saw",
+            Self::Triangle => "triangle",
+            Self::Pulse(_) => "pulse",
+        }
+    }
+}
+
+/// Phase-accumulator oscillator. Discontinuities are smoothed with PolyBLEP
+/// (steps) and PolyBLAMP (corners) so partials above Nyquist are suppressed.
+#[derive(Debug, Clone)]
+pub struct Oscillator {
+    pub waveform: Waveform,
+    phase: f64,
+}
+
+impl Oscillator {
+    pub fn new(waveform: Waveform) -> Self {
+        Self::with_phase(waveform, 0.0)
+    }
+
+    /// Starts the oscillator at `phase` cycles (0.25 is a quarter period).
+    pub fn with_phase(waveform: Waveform, phase: f64) -> Self {
+        Self { waveform, phase: wrap(phase) }
+    }
+
+    /// Returns the sample at the current phase, then advances by
+    /// `increment` cycles (frequency divided by sample rate).
+    pub fn next_sample(&mut self, increment: f64) -> f64 {
+        let t = self.phase;
+        let dt = increment.abs().min(0.5);
+
+        let sample = match self.waveform {
+            Waveform::Sine => (2.0 * PI * t).sin(),
+            Waveform::Saw => {
+                let t = wrap(t + 0.5);
+                2.0 * t - 1.0 - poly_blep(t, dt)
+            }
+            Waveform::Square => pulse(t, 0.5, dt),
+            Waveform::Pulse(width) => pulse(t, width, dt),
+            Waveform::Triangle => {
+                let naive = if t < 0.25 {
+                    4.0 * t
+                } else if t < 0.75 {
+                    2.0 - 4.0 * t
+                } else {
+                    4.0 * t - 4.0
+                };
+                naive + 4.0 * dt * (poly_blamp(wrap(t + 0.25), dt) - poly_blamp(wrap(t + 0.75), dt))
+            }
+        };
+
+        self.phase = wrap(self.phase + increment);
+        sample
+    }
+}
+
+fn pulse(t: f64, width: f64, dt: f64) -> f64 {
+    let naive = if t < width { 1.0 } else { -1.0 };
+    naive + poly_blep(t, dt) - poly_blep(wrap(t + 1.0 - width), dt)
+}
+
+fn wrap(phase: f64) -> f64 {
+    phase - phase.floor()
+}
+
+fn poly_blep(t: f64, dt: f64) -> f64 {
+    if dt <= 0.0 {
+        0.0
+    } else if t < dt {
+        let t = t / dt;
+        t + t - t * t - 1.0
+    } else if t > 1.0 - dt {
+        let t = (t - 1.0) / dt;
+        t * t + t + t + 1.0
+    } else {
+        0.0
+    }
+}
+
+fn poly_blamp(t: f64, dt: f64) -> f64 {
+    if dt <= 0.0 {
+        0.0
+    } else if t < dt {
+        let t = t / dt - 1.0;
+        -t * t * t / 3.0
+    } else if t > 1.0 - dt {
+        let t = (t - 1.0) / dt + 1.0;
+        t * t * t / 3.0
+    } else {
+        0.0
+    }
+}
+
+/// Parameters shared by every oscillator source: output scaling, starting
+/// phase in degrees and the time window in which the oscillator runs.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OscillatorParams {
+    pub amp: f64,
+    pub phase: f64,
+    pub offset: f64,
+    pub start: f64,
+    pub stop: Option<f64>,
+}
+
+impl Default for OscillatorParams {
+    fn default() -> Self {
+        Self { amp: 1.0, phase: 0.0, offset: 0.0, start: 0.0, stop: None }
+    }
+}
+
+impl OscillatorParams {
+    /// Applies `key=value` if it is one of the shared parameters. Returns
+    /// `false` for keys the calling source has to handle itself.
+    pub fn parse_param(&mut self, key: &str, value: &str) -> Result<bool> {
+        match key {
+            "amp" => self.amp = value.parse().map_err(|_| eyre!("Invalid amp value"))?,
+            "phase" => self.phase = value.parse().map_err(|_| eyre!("Invalid phase value"))?,
+            "offset" => self.offset = value.parse().map_err(|_| eyre!("Invalid offset value"))?,
+            "start" => self.start = value.parse().map_err(|_| eyre!("Invalid start value"))?,
+            "stop" => self.stop = Some(value.parse().map_err(|_| eyre!("Invalid stop value"))?),
+            _ => return Ok(false),
+        }
+        Ok(true)
+    }
+
+    pub fn validate(&self) -> Result<()> {
+        if self.start < 0.0 {
+            bail!("Oscillator start must not be negative");
+        }
+        if let Some(stop) = self.stop
+            && stop <= self.start
+        {
+            bail!("Oscillator stop must be after start");
+        }
+        Ok(())
+    }
+
+    /// An oscillator positioned at the configured starting phase.
+    pub fn oscillator(&self, waveform: Waveform) -> Oscillator {
+        Oscillator::with_phase(waveform, self.phase / 360.0)
+    }
+
+    /// Seconds the oscillator runs for within a buffer of `duration` seconds.
+    pub fn active_duration(&self, duration: f64) -> f64 {
+        (self.stop.unwrap_or(duration).min(duration) - self.start).max(0.0)
+    }
+
+    /// Renders `duration` seconds. `next_sample` is called once per sample
+    /// between `start` and `stop` with the index since `start`; its output is
+    /// scaled by `amp` and shifted by `offset`. Outside that window the
+    /// output is silent.
+    pub fn render(
+        &self,
+        duration: f64,
+        sample_rate: f64,
+        mut next_sample: impl FnMut(usize) -> f64,
+    ) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        let first = ((self.start * sample_rate).round() as usize).min(num_samples);
+        let last = self
+            .stop
+            .map_or(num_samples, |stop| (stop * sample_rate).round() as usize)
+            .clamp(first, num_samples);
+
+        let mut samples = vec![0.0; num_samples];
+        for (i, sample) in samples[first..last].iter_mut().enumerate() {
+            *sample = self.amp * next_sample(i) + self.offset;
+        }
+        samples
+    }
+}
+
+impl std::fmt::Display for OscillatorParams {
+    /// Formats the parameters that differ from their defaults as a spec
+    /// suffix, e.g. `:amp=0.3:phase=90`.
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let defaults = Self::default();
+        if self.amp != defaults.amp {
+            write!(f, ":amp={}", self.amp)?;
+        }
+        if self.phase != defaults.phase {
+            write!(f, ":phase={}", self.phase)?;
+        }
+        if self.offset != defaults.offset {
+            write!(f, ":offset={}", self.offset)?;
+        }
+        if self.start != defaults.start {
+            write!(f, ":start={}", self.start)?;
+        }
+        if let Some(stop) = self.stop {
+            write!(f, ":stop={}", stop)?;
+        }
+        Ok(())
+    }
+}
+
+/// Parameters of the plain single-frequency oscillators (`sine`, `square`,
+/// `saw`, `triangle`).
+pub struct ToneParams {
+    pub freq: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for ToneParams {
+    fn default() -> Self {
+        Self { freq: 440.0, oscillator: OscillatorParams::default() }
+    }
+}
+
+impl ToneParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+#[instrument(level = "debug", fields(waveform = ?waveform, frequency = %frequency, duration = %duration, sample_rate = %sample_rate))]
+pub fn generate_waveform(
+    waveform: Waveform,
+    frequency: f64,
+    duration: f64,
+    sample_rate: f64,
+) -> Vec<f64> {
+    let num_samples = (duration * sample_rate) as usize;
+    debug!("Generating {} samples of {:?} at {} Hz", num_samples, waveform, frequency);
+
+    let mut oscillator = Oscillator::new(waveform);
+    let increment = frequency / sample_rate;
+    let samples: Vec<f64> = (0..num_samples).map(|_| oscillator.next_sample(increment)).collect();
+
+    debug!("Waveform generation complete: {} samples", samples.len());
+    samples
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::factory::create_component;
+
+    fn render(spec: &str, duration: f64, sample_rate: f64) -> Vec<f64> {
+        create_component(spec).unwrap().get_samples(duration, sample_rate).unwrap()
+    }
+
+    #[test]
+    fn saw_runs_at_requested_frequency() {
+        let samples = render("saw:freq=440", 1.0, 44100.0);
+        let rising = samples.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        // The first cycle starts on a crossing at sample 0, which is not counted
+        assert!((439..=440).contains(&rising), "{} rising zero crossings in 1 s", rising);
+    }
+
+    /// Fraction of the signal's energy, in dB, that is not at a multiple of
+    /// `frequency`, i.e. partials above Nyquist that folded back. Over exactly
+    /// 1 s every component lands on a whole-Hz DFT bin.
+    fn alias_energy(samples: &[f64], frequency: f64, sample_rate: f64) -> f64 {
+        let total: f64 = samples.iter().map(|s| s * s).sum();
+        let harmonic: f64 = (1..)
+            .map(|k| k as f64 * frequency)
+            .take_while(|&f| f < sample_rate / 2.0)
+            .map(|f| {
+                let (mut re, mut im) = (0.0, 0.0);
+                for (n, s) in samples.iter().enumerate() {
+                    let phase = 2.0 * PI * f * n as f64 / sample_rate;
+                    re += s * phase.cos();
+                    im += s * phase.sin();
+                }
+                2.0 * (re * re + im * im) / samples.len() as f64
+            })
+            .sum();
+        10.0 * ((total - harmonic) / total).log10()
+    }
+
+    #[test]
+    fn high_square_suppresses_aliases() {
+        let sample_rate = 44100.0;
+        let frequency = 3001.0;
+        let band_limited = render("square:freq=3001", 1.0, sample_rate);
+        let naive: Vec<f64> = (0..44100)
+            .map(|n| if (frequency * n as f64 / sample_rate).fract() < 0.5 { 1.0 } else { -1.0 })
+            .collect();
+
+        let aliased = alias_energy(&band_limited, frequency, sample_rate);
+        let reference = alias_energy(&naive, frequency, sample_rate);
+        assert!(aliased < -25.0, "alias energy {:.1} dB", aliased);
+        assert!(
+            aliased < reference - 10.0,
+            "alias energy {:.1} dB vs naive {:.1} dB",
+            aliased,
+            reference
+        );
+    }
+
+    /// Continuous 100 Hz tones from sources that share `OscillatorParams`.
+    const TONES: [&str; 3] =
+        ["sine:freq=100", "saw:freq=100", "burst:freq=100:cycles=100:period=1"];
+
+    fn assert_close(actual: &[f64], expected: &[f64], context: &str) {
+        assert_eq!(actual.len(), expected.len(), "{}", context);
+        for (i, (a, e)) in actual.iter().zip(expected).enumerate() {
+            assert!((a - e).abs() < 1e-9, "{}: sample {} is {} not {}", context, i, a, e);
+        }
+    }
+
+    #[test]
+    fn phase_shifts_every_source_alike() {
+        // 90 degrees of 100 Hz at 8 kHz is 20 samples
+        for spec in TONES {
+            let base = render(spec, 0.5, 8000.0);
+            let shifted = render(&format!("{}:phase=90", spec), 0.5, 8000.0);
+            assert_close(&shifted[..3000], &base[20..3020], spec);
+        }
+    }
+
+    #[test]
+    fn offset_and_amp_apply_to_every_source() {
+        for spec in TONES {
+            let base = render(spec, 0.5, 8000.0);
+            let scaled = render(&format!("{}:amp=0.5:offset=0.25", spec), 0.5, 8000.0);
+            let expected: Vec<f64> = base.iter().map(|s| 0.5 * s + 0.25).collect();
+            assert_close(&scaled, &expected, spec);
+        }
+    }
+
+    #[test]
+    fn start_and_stop_window_every_source() {
+        for spec in TONES {
+            let base = render(spec, 0.5, 8000.0);
+            let windowed = render(&format!("{}:start=0.1:stop=0.2", spec), 0.5, 8000.0);
+            assert_eq!(windowed.len(), base.len(), "{}", spec);
+            assert!(windowed[..800].iter().all(|&s| s == 0.0), "{} before start", spec);
+            assert!(windowed[1600..].iter().all(|&s| s == 0.0), "{} after stop", spec);
+            // The oscillator starts from its initial phase at `start`
+            assert_close(&windowed[800..1600], &base[..800], spec);
+        }
+    }
+}
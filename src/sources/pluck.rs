@@ -0,0 +1,259 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+    warn,
+};
+
+use crate::random::Rng;
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct PluckParams {
+    pub freq: f64,
+    pub decay: f64,
+    pub brightness: f64,
+    pub seed: u64,
+    pub amp: f64,
+}
+
+impl Default for PluckParams {
+    fn default() -> Self {
+        Self { freq: 220.0, decay: 2.0, brightness: 0.8, seed: 0, amp: 1.0 }
+    }
+}
+
+impl PluckParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "decay" => {
+                    result.decay = kv[1].parse().map_err(|_| eyre!("Invalid decay value"))?
+                }
+                "brightness" => {
+                    result.brightness =
+                        kv[1].parse().map_err(|_| eyre!("Invalid brightness value"))?
+                }
+                "seed" => result.seed = kv[1].parse().map_err(|_| eyre!("Invalid seed value"))?,
+                "amp" => result.amp = kv[1].parse().map_err(|_| eyre!("Invalid amp value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.freq <= 0.0 || result.decay <= 0.0 {
+            bail!("pluck freq and decay must be positive");
+        }
+        if !(0.0..=1.0).contains(&result.brightness) {
+            bail!("pluck brightness must be between 0 and 1");
+        }
+        Ok(result)
+    }
+}
+
+/// The string itself: a delay line closed by a two-point averaging lowpass
+/// and a first-order allpass that supplies the fractional part of the period.
+struct KarplusStrong {
+    delay_line: Vec<f64>,
+    index: usize,
+    loop_gain: f64,
+    allpass_coefficient: f64,
+    previous_output: f64,
+    allpass_input: f64,
+    allpass_output: f64,
+}
+
+impl KarplusStrong {
+    fn new(frequency: f64, decay: f64, sample_rate: f64, excitation: Vec<f64>) -> Self {
+        let (length, fraction) = Self::tuning(frequency, sample_rate);
+        let mut delay_line = excitation;
+        delay_line.resize(length, 0.0);
+
+        Self {
+            delay_line,
+            index: 0,
+            // -60 dB after `decay` seconds, i.e. `decay * frequency` trips round the loop
+            loop_gain: 10f64.powf(-3.0 / (decay * frequency)),
+            allpass_coefficient: (1.0 - fraction) / (1.0 + fraction),
+            previous_output: 0.0,
+            allpass_input: 0.0,
+            allpass_output: 0.0,
+        }
+    }
+
+    /// Splits the loop period into whole samples and an allpass fraction,
+    /// keeping the fraction in `[0.1, 1.1)` where the allpass is well behaved.
+    /// The averaging filter contributes the other half sample.
+    fn tuning(frequency: f64, sample_rate: f64) -> (usize, f64) {
+        let period = (sample_rate / frequency - 0.5).max(1.1);
+        let mut length = period.floor();
+        let mut fraction = period - length;
+        if fraction < 0.1 && length > 1.0 {
+            length -= 1.0;
+            fraction += 1.0;
+        }
+        (length.max(1.0) as usize, fraction)
+    }
+
+    fn next_sample(&mut self) -> f64 {
+        let output = self.delay_line[self.index];
+
+        let averaged = 0.5 * (output + self.previous_output);
+        self.previous_output = output;
+
+        let c = self.allpass_coefficient;
+        let tuned = c * averaged + self.allpass_input - c * self.allpass_output;
+        self.allpass_input = averaged;
+        self.allpass_output = tuned;
+
+        self.delay_line[self.index] = self.loop_gain * tuned;
+        self.index = (self.index + 1) % self.delay_line.len();
+        output
+    }
+}
+
+pub struct PluckSource {
+    pub frequency: f64,
+    pub decay: f64,
+    pub brightness: f64,
+    pub seed: u64,
+    pub amp: f64,
+}
+
+impl PluckSource {
+    #[instrument(level = "debug", fields(frequency = %frequency, decay = %decay, brightness = %brightness, seed = %seed))]
+    pub fn new(frequency: f64, decay: f64, brightness: f64, seed: u64, amp: f64) -> Self {
+        debug!(
+            "Creating pluck source at {} Hz (decay {} s, brightness {})",
+            frequency, decay, brightness
+        );
+        Self { frequency, decay, brightness, seed, amp }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "pluck" {
+            bail!("Not a pluck spec");
+        }
+        let params = PluckParams::parse(&parts[1..])?;
+        debug!("Pluck source created at {} Hz from spec", params.freq);
+        Ok(Self::new(params.freq, params.decay, params.brightness, params.seed, params.amp))
+    }
+
+    /// Seeded noise burst filling one period, darkened by a one-pole lowpass
+    /// as brightness drops and with its DC removed so the string rings
+    /// around zero.
+    fn excitation(&self, length: usize) -> Vec<f64> {
+        let mut rng = Rng::new(self.seed);
+        let smoothing = (1.0 - self.brightness) * 0.95;
+        let mut state = 0.0;
+        let mut burst: Vec<f64> = (0..length)
+            .map(|_| {
+                state = (1.0 - smoothing) * rng.next_bipolar() + smoothing * state;
+                state
+            })
+            .collect();
+
+        let mean = burst.iter().sum::<f64>() / length.max(1) as f64;
+        let peak = burst.iter().fold(0.0f64, |acc, &x| acc.max((x - mean).abs()));
+        for sample in &mut burst {
+            *sample = if peak > 0.0 { (*sample - mean) / peak } else { 0.0 };
+        }
+        burst
+    }
+}
+
+impl Source for PluckSource {
+    #[instrument(skip(self), fields(frequency = %self.frequency, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!("Generating {} samples of plucked string at {} Hz", num_samples, self.frequency);
+
+        let (length, _) = KarplusStrong::tuning(self.frequency, sample_rate);
+        let mut string =
+            KarplusStrong::new(self.frequency, self.decay, sample_rate, self.excitation(length));
+        let samples: Vec<f64> = (0..num_samples).map(|_| self.amp * string.next_sample()).collect();
+
+        debug!("Pluck generation complete: {} samples", samples.len());
+        samples
+    }
+}
+
+impl Component for PluckSource {
+    #[instrument(skip(self, buffer), fields(frequency = %self.frequency))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing pluck source");
+        if self.frequency >= sample_rate / 2.0 {
+            bail!(
+                "pluck freq {} Hz must be below Nyquist ({} Hz)",
+                self.frequency,
+                sample_rate / 2.0
+            );
+        }
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        if self.frequency >= sample_rate / 2.0 {
+            warn!("pluck freq {} Hz is above Nyquist, producing no samples", self.frequency);
+            return None;
+        }
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "pluck:freq={}:decay={}:brightness={}:seed={}:amp={}",
+            self.frequency, self.decay, self.brightness, self.seed, self.amp
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rejects_frequencies_above_nyquist() {
+        let mut pluck = PluckSource::from_spec("pluck:freq=30000").unwrap();
+        let mut buffer = Vec::new();
+        assert!(Component::process(&mut pluck, &mut buffer, 0.1, 44100.0).is_err());
+        assert!(pluck.get_samples(0.1, 44100.0).is_none());
+        assert!(Component::process(&mut pluck, &mut buffer, 0.1, 96000.0).is_ok());
+    }
+
+    #[test]
+    fn rings_at_requested_pitch() {
+        let samples =
+            PluckSource::from_spec("pluck:freq=220:decay=5").unwrap().generate(1.0, 44100.0);
+        // Autocorrelation peaks at the loop period of 44100 / 220 ≈ 200.5 samples
+        let settled = &samples[4410..30000];
+        let correlation = |lag: usize| -> f64 {
+            settled[..settled.len() - 300].iter().zip(&settled[lag..]).map(|(a, b)| a * b).sum()
+        };
+        let best = (150..260).max_by(|&a, &b| correlation(a).total_cmp(&correlation(b))).unwrap();
+        assert!((200..=201).contains(&best), "period {} samples", best);
+    }
+}
@@ -0,0 +1,137 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    OscillatorParams,
+    Waveform,
+    generate_waveform,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct PulseParams {
+    pub freq: f64,
+    pub width: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for PulseParams {
+    fn default() -> Self {
+        Self { freq: 440.0, width: 0.5, oscillator: OscillatorParams::default() }
+    }
+}
+
+impl PulseParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "width" => {
+                    result.width = kv[1].parse().map_err(|_| eyre!("Invalid width value"))?
+                }
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        if result.width <= 0.0 || result.width >= 1.0 {
+            bail!("Pulse width must be between 0 and 1 (exclusive)");
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+pub struct PulseWaveSource {
+    pub frequency: f64,
+    pub width: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl PulseWaveSource {
+    #[instrument(level = "debug", fields(frequency = %frequency, width = %width))]
+    pub fn new(frequency: f64, width: f64, oscillator: OscillatorParams) -> Self {
+        debug!("Creating pulse wave source at {} Hz with width {}", frequency, width);
+        Self { frequency, width, oscillator }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "pulse" {
+            bail!("Not a pulse spec");
+        }
+        let params = PulseParams::parse(&parts[1..])?;
+        debug!(
+            "Pulse wave source created at {} Hz with width {} from spec",
+            params.freq, params.width
+        );
+        Ok(Self::new(params.freq, params.width, params.oscillator))
+    }
+}
+
+impl Source for PulseWaveSource {
+    #[instrument(skip(self), fields(frequency = %self.frequency, width = %self.width, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        debug!(
+            "Generating pulse wave: {} Hz (width {}) for {} seconds at {} Hz sample rate",
+            self.frequency, self.width, duration, sample_rate
+        );
+        let mut oscillator = self.oscillator.oscillator(Waveform::Pulse(self.width));
+        let increment = self.frequency / sample_rate;
+        self.oscillator.render(duration, sample_rate, |_| oscillator.next_sample(increment))
+    }
+}
+
+impl Component for PulseWaveSource {
+    #[instrument(skip(self, buffer), fields(frequency = %self.frequency, width = %self.width))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing pulse wave source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("pulse:freq={}:width={}{}", self.frequency, self.width, self.oscillator)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[instrument(level = "debug", fields(frequency = %frequency, width = %width, duration = %duration, sample_rate = %sample_rate))]
+pub fn generate_pulse_wave(
+    frequency: f64,
+    width: f64,
+    duration: f64,
+    sample_rate: f64,
+) -> Vec<f64> {
+    generate_waveform(Waveform::Pulse(width), frequency, duration, sample_rate)
+}
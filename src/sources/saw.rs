@@ -0,0 +1,85 @@
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    OscillatorParams,
+    ToneParams,
+    Waveform,
+    generate_waveform,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct SawWaveSource {
+    pub frequency: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl SawWaveSource {
+    #[instrument(level = "debug", fields(frequency = %frequency))]
+    pub fn new(frequency: f64, oscillator: OscillatorParams) -> Self {
+        debug!("Creating sawtooth wave source at {} Hz", frequency);
+        Self { frequency, oscillator }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "saw" {
+            bail!("Not a saw spec");
+        }
+        let params = ToneParams::parse(&parts[1..])?;
+        debug!("Sawtooth wave source created at {} Hz from spec", params.freq);
+        Ok(Self::new(params.freq, params.oscillator))
+    }
+}
+
+impl Source for SawWaveSource {
+    #[instrument(skip(self), fields(frequency = %self.frequency, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        debug!(
+            "Generating sawtooth wave: {} Hz for {} seconds at {} Hz sample rate",
+            self.frequency, duration, sample_rate
+        );
+        let mut oscillator = self.oscillator.oscillator(Waveform::Saw);
+        let increment = self.frequency / sample_rate;
+        self.oscillator.render(duration, sample_rate, |_| oscillator.next_sample(increment))
+    }
+}
+
+impl Component for SawWaveSource {
+    #[instrument(skip(self, buffer), fields(frequency = %self.frequency))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing sawtooth wave source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("saw:freq={}{}", self.frequency, self.oscillator)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[instrument(level = "debug", fields(frequency = %frequency, duration = %duration, sample_rate = %sample_rate))]
+pub fn generate_saw_wave(frequency: f64, duration: f64, sample_rate: f64) -> Vec<f64> {
+    generate_waveform(Waveform::Saw, frequency, duration, sample_rate)
+}
@@ -0,0 +1,282 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    Oscillator,
+    Waveform,
+};
+use crate::processors::{
+    Curve,
+    EnvelopeProcessor,
+    EnvelopeShape,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+/// Attack and release applied to every note so that note boundaries do not
+/// click.
+const NOTE_RAMP: f64 = 0.005;
+
+/// Frequency in Hz of a MIDI note number (A4 = 69 = 440 Hz).
+pub fn midi_to_frequency(note: f64) -> f64 {
+    440.0 * 2f64.powf((note - 69.0) / 12.0)
+}
+
+/// Parses scientific pitch notation (`C4`, `F#3`, `Bb2`, `c-1`) into a MIDI
+/// note number.
+pub fn parse_note_name(name: &str) -> Result<i32> {
+    let mut chars = name.chars();
+    let letter = chars.next().ok_or_else(|| eyre!("Empty note name"))?;
+    let mut semitone = match letter.to_ascii_uppercase() {
+        'C' => 0,
+        'D' => 2,
+        'E' => 4,
+        'F' => 5,
+        'G' => 7,
+        'A' => 9,
+        'B' => 11,
+        _ => bail!("Invalid note name: {}", name),
+    };
+
+    let rest = chars.as_str();
+    let accidentals = rest.find(|c| c != '#' && c != 'b').unwrap_or(rest.len());
+    for accidental in rest[..accidentals].chars() {
+        semitone += if accidental == '#' { 1 } else { -1 };
+    }
+
+    let octave: i32 =
+        rest[accidentals..].parse().map_err(|_| eyre!("Invalid octave in note: {}", name))?;
+    Ok(12 * (octave + 1) + semitone)
+}
+
+/// Adds one note filling `buffer` to it, with short linear ramps at both
+/// ends.
+pub(crate) fn render_note(
+    buffer: &mut [f64],
+    waveform: Waveform,
+    frequency: f64,
+    gain: f64,
+    sample_rate: f64,
+) {
+    let total = buffer.len() as f64 / sample_rate;
+    let ramp = NOTE_RAMP.min(total / 2.0);
+    let envelope = EnvelopeProcessor::new(
+        EnvelopeShape::Adsr { attack: ramp, decay: 0.0, sustain: 1.0, release: ramp },
+        Curve::Linear,
+    );
+    let mut oscillator = Oscillator::new(waveform);
+    let increment = frequency / sample_rate;
+    for (i, sample) in buffer.iter_mut().enumerate() {
+        let level = envelope.gain_at(i as f64 / sample_rate, total);
+        *sample += gain * level * oscillator.next_sample(increment);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Note {
+    pub pitch: String,
+    /// `None` for a rest.
+    pub frequency: Option<f64>,
+    pub duration: f64,
+    pub velocity: f64,
+}
+
+impl Note {
+    /// Parses `NAME/DURATION[/VELOCITY]`, where `NAME` is a note such as
+    /// `C#4` or `R` for a rest, and `DURATION` is in seconds.
+    pub fn parse(spec: &str) -> Result<Self> {
+        let fields: Vec<&str> = spec.split('/').map(|f| f.trim()).collect();
+        if fields.len() < 2 || fields.len() > 3 {
+            bail!("Invalid note: {} (expected NAME/DURATION[/VELOCITY])", spec);
+        }
+
+        let frequency = match fields[0] {
+            "R" | "r" | "-" => None,
+            name => Some(midi_to_frequency(parse_note_name(name)? as f64)),
+        };
+        let duration: f64 =
+            fields[1].parse().map_err(|_| eyre!("Invalid note duration: {}", fields[1]))?;
+        let velocity: f64 = match fields.get(2) {
+            Some(v) => v.parse().map_err(|_| eyre!("Invalid note velocity: {}", v))?,
+            None => 1.0,
+        };
+        if duration < 0.0 {
+            bail!("Note duration must not be negative: {}", spec);
+        }
+        Ok(Self { pitch: fields[0].to_string(), frequency, duration, velocity })
+    }
+}
+
+pub struct SeqParams {
+    pub wave: Waveform,
+    pub notes: Vec<Note>,
+    pub gap: f64,
+}
+
+impl Default for SeqParams {
+    fn default() -> Self {
+        Self { wave: Waveform::Sine, notes: Vec::new(), gap: 0.0 }
+    }
+}
+
+impl SeqParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "wave" => result.wave = Waveform::parse(kv[1])?,
+                "notes" => {
+                    let Some(list) = kv[1].strip_prefix('[').and_then(|l| l.strip_suffix(']'))
+                    else {
+                        bail!("seq notes must be enclosed in [ ]: notes=[C4/0.25,E4/0.25]");
+                    };
+                    result.notes = list
+                        .split(',')
+                        .filter(|n| !n.trim().is_empty())
+                        .map(Note::parse)
+                        .collect::<Result<_>>()?;
+                }
+                "gap" => result.gap = kv[1].parse().map_err(|_| eyre!("Invalid gap value"))?,
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        if result.notes.is_empty() {
+            bail!("seq requires notes: seq:wave=sine:notes=[C4/0.25,E4/0.25,G4/0.5]");
+        }
+        if result.gap < 0.0 {
+            bail!("seq gap must not be negative");
+        }
+        Ok(result)
+    }
+}
+
+pub struct SeqSource {
+    pub wave: Waveform,
+    pub notes: Vec<Note>,
+    pub gap: f64,
+}
+
+impl SeqSource {
+    #[instrument(level = "debug", skip(notes), fields(wave = ?wave, num_notes = %notes.len(), gap = %gap))]
+    pub fn new(wave: Waveform, notes: Vec<Note>, gap: f64) -> Self {
+        debug!("Creating {} sequence of {} notes", wave.name(), notes.len());
+        Self { wave, notes, gap }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "seq" {
+            bail!("Not a seq spec");
+        }
+        let params = SeqParams::parse(&parts[1..])?;
+        debug!("Sequence of {} notes created from spec", params.notes.len());
+        Ok(Self::new(params.wave, params.notes, params.gap))
+    }
+
+    /// Total length of the melody in seconds.
+    pub fn length(&self) -> f64 {
+        self.notes.iter().map(|n| n.duration).sum()
+    }
+}
+
+impl Source for SeqSource {
+    #[instrument(skip(self), fields(num_notes = %self.notes.len(), duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!(
+            "Rendering {} notes ({} s) into {} samples",
+            self.notes.len(),
+            self.length(),
+            num_samples
+        );
+
+        let mut samples = vec![0.0; num_samples];
+        let mut note_start = 0.0;
+        for note in &self.notes {
+            let first = (note_start * sample_rate).round() as usize;
+            note_start += note.duration;
+            let Some(frequency) = note.frequency else {
+                continue;
+            };
+
+            let sounding = (note.duration - self.gap).max(0.0);
+            let last = ((first as f64 + sounding * sample_rate).round() as usize).min(num_samples);
+            if first >= last {
+                continue;
+            }
+
+            render_note(
+                &mut samples[first..last],
+                self.wave,
+                frequency,
+                note.velocity,
+                sample_rate,
+            );
+        }
+
+        debug!("Sequence rendering complete: {} samples", samples.len());
+        samples
+    }
+}
+
+impl Component for SeqSource {
+    #[instrument(skip(self, buffer), fields(num_notes = %self.notes.len()))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing seq source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        let notes: Vec<String> = self
+            .notes
+            .iter()
+            .map(|n| format!("{}/{}/{}", n.pitch, n.duration, n.velocity))
+            .collect();
+        format!("seq:wave={}:gap={}:notes=[{}]", self.wave.name(), self.gap, notes.join(","))
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn notes_require_brackets() {
+        let seq = SeqSource::from_spec("seq:notes=[C4/0.25,e4/0.25,R/0.1,G#4/0.5/0.8]").unwrap();
+        let pitches: Vec<&str> = seq.notes.iter().map(|n| n.pitch.as_str()).collect();
+        assert_eq!(pitches, ["C4", "e4", "R", "G#4"]);
+        assert_eq!(seq.notes[3].velocity, 0.8);
+
+        assert!(SeqSource::from_spec("seq:notes=C4/0.25").is_err());
+        assert!(SeqSource::from_spec("seq:notes=[C4/0.25").is_err());
+    }
+}
@@ -0,0 +1,76 @@
+use color_eyre::Result;
+use color_eyre::eyre::bail;
+use tracing::{
+    debug,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct SilenceSource;
+
+impl SilenceSource {
+    #[instrument(level = "debug")]
+    pub fn new() -> Self {
+        debug!("Creating silence source");
+        Self
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "silence" {
+            bail!("Not a silence spec");
+        }
+        // No parameters expected for silence
+        if parts.len() > 1 {
+            bail!("silence takes no params: silence");
+        }
+        debug!("Silence source created from spec");
+        Ok(Self::new())
+    }
+}
+
+impl Default for SilenceSource {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Source for SilenceSource {
+    #[instrument(skip(self), fields(duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!("Generating {} samples of silence", num_samples);
+        vec![0.0; num_samples]
+    }
+}
+
+impl Component for SilenceSource {
+    #[instrument(skip(self, buffer))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing silence source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        "silence".to_string()
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
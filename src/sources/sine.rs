@@ -1,55 +1,31 @@
 use color_eyre::Result;
-use color_eyre::eyre::{
-    bail,
-    eyre,
-};
+use color_eyre::eyre::bail;
 use tracing::{
     debug,
     instrument,
 };
 
+use super::oscillator::{
+    OscillatorParams,
+    ToneParams,
+    Waveform,
+    generate_waveform,
+};
 use crate::traits::{
     Component,
     Source,
 };
 
-pub struct SineParams {
-    pub freq: f64,
-}
-
-impl Default for SineParams {
-    fn default() -> Self {
-        Self { freq: 440.0 }
-    }
-}
-
-impl SineParams {
-    #[instrument]
-    pub fn parse(params: &[&str]) -> Result<Self> {
-        let mut result = Self::default();
-        for param in params {
-            let kv: Vec<&str> = param.split('=').collect();
-            if kv.len() != 2 {
-                bail!("Invalid parameter format: {}", param);
-            }
-            match kv[0] {
-                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
-                _ => bail!("Unknown parameter: {}", kv[0]),
-            }
-        }
-        Ok(result)
-    }
-}
-
 pub struct SineWaveSource {
     pub frequency: f64,
+    pub oscillator: OscillatorParams,
 }
 
 impl SineWaveSource {
     #[instrument(level = "debug", fields(frequency = %frequency))]
-    pub fn new(frequency: f64) -> Self {
+    pub fn new(frequency: f64, oscillator: OscillatorParams) -> Self {
         debug!("Creating sine wave source at {} Hz", frequency);
-        Self { frequency }
+        Self { frequency, oscillator }
     }
 
     #[instrument(level = "debug")]
@@ -58,9 +34,9 @@ impl SineWaveSource {
         if parts[0] != "sine" {
             bail!("Not a sine spec");
         }
-        let params = SineParams::parse(&parts[1..])?;
+        let params = ToneParams::parse(&parts[1..])?;
         debug!("Sine wave source created at {} Hz from spec", params.freq);
-        Ok(Self::new(params.freq))
+        Ok(Self::new(params.freq, params.oscillator))
     }
 }
 
@@ -71,7 +47,9 @@ impl Source for SineWaveSource {
             "Generating sine wave: {} Hz for {} seconds at {} Hz sample rate",
             self.frequency, duration, sample_rate
         );
-        generate_sine_wave(self.frequency, duration, sample_rate)
+        let mut oscillator = self.oscillator.oscillator(Waveform::Sine);
+        let increment = self.frequency / sample_rate;
+        self.oscillator.render(duration, sample_rate, |_| oscillator.next_sample(increment))
     }
 }
 
@@ -93,7 +71,7 @@ impl Component for SineWaveSource {
     }
 
     fn name(&self) -> String {
-        format!("sine:freq={}", self.frequency)
+        format!("sine:freq={}{}", self.frequency, self.oscillator)
     }
 
     fn component_type(&self) -> &'static str {
@@ -103,18 +81,5 @@ impl Component for SineWaveSource {
 
 #[instrument(level = "debug", fields(frequency = %frequency, duration = %duration, sample_rate = %sample_rate))]
 pub fn generate_sine_wave(frequency: f64, duration: f64, sample_rate: f64) -> Vec<f64> {
-    let num_samples = (duration * sample_rate) as usize;
-    debug!("Generating {} sine wave samples at {} Hz", num_samples, frequency);
-
-    let mut samples = Vec::with_capacity(num_samples);
-    let angular_frequency = 2.0 * std::f64::consts::PI * frequency;
-
-    for i in 0..num_samples {
-        let t = i as f64 / sample_rate;
-        let sample = (angular_frequency * t).sin();
-        samples.push(sample);
-    }
-
-    debug!("Sine wave generation complete: {} samples", samples.len());
-    samples
+    generate_waveform(Waveform::Sine, frequency, duration, sample_rate)
 }
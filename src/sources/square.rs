@@ -1,55 +1,31 @@
 use color_eyre::Result;
-use color_eyre::eyre::{
-    bail,
-    eyre,
-};
+use color_eyre::eyre::bail;
 use tracing::{
     debug,
     instrument,
 };
 
+use super::oscillator::{
+    OscillatorParams,
+    ToneParams,
+    Waveform,
+    generate_waveform,
+};
 use crate::traits::{
     Component,
     Source,
 };
 
-pub struct SquareParams {
-    pub freq: f64,
-}
-
-impl Default for SquareParams {
-    fn default() -> Self {
-        Self { freq: 440.0 }
-    }
-}
-
-impl SquareParams {
-    #[instrument]
-    pub fn parse(params: &[&str]) -> Result<Self> {
-        let mut result = Self::default();
-        for param in params {
-            let kv: Vec<&str> = param.split('=').collect();
-            if kv.len() != 2 {
-                bail!("Invalid parameter format: {}", param);
-            }
-            match kv[0] {
-                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
-                _ => bail!("Unknown parameter: {}", kv[0]),
-            }
-        }
-        Ok(result)
-    }
-}
-
 pub struct SquareWaveSource {
     pub frequency: f64,
+    pub oscillator: OscillatorParams,
 }
 
 impl SquareWaveSource {
     #[instrument(level = "debug", fields(frequency = %frequency))]
-    pub fn new(frequency: f64) -> Self {
+    pub fn new(frequency: f64, oscillator: OscillatorParams) -> Self {
         debug!("Creating square wave source at {} Hz", frequency);
-        Self { frequency }
+        Self { frequency, oscillator }
     }
 
     #[instrument(level = "debug")]
@@ -58,9 +34,9 @@ impl SquareWaveSource {
         if parts[0] != "square" {
             bail!("Not a square spec");
         }
-        let params = SquareParams::parse(&parts[1..])?;
+        let params = ToneParams::parse(&parts[1..])?;
         debug!("Square wave source created at {} Hz from spec", params.freq);
-        Ok(Self::new(params.freq))
+        Ok(Self::new(params.freq, params.oscillator))
     }
 }
 
@@ -71,7 +47,9 @@ impl Source for SquareWaveSource {
             "Generating square wave: {} Hz for {} seconds at {} Hz sample rate",
             self.frequency, duration, sample_rate
         );
-        generate_square_wave(self.frequency, duration, sample_rate)
+        let mut oscillator = self.oscillator.oscillator(Waveform::Square);
+        let increment = self.frequency / sample_rate;
+        self.oscillator.render(duration, sample_rate, |_| oscillator.next_sample(increment))
     }
 }
 
@@ -93,7 +71,7 @@ impl Component for SquareWaveSource {
     }
 
     fn name(&self) -> String {
-        format!("square:freq={}", self.frequency)
+        format!("square:freq={}{}", self.frequency, self.oscillator)
     }
 
     fn component_type(&self) -> &'static str {
@@ -103,18 +81,5 @@ impl Component for SquareWaveSource {
 
 #[instrument(level = "debug", fields(frequency = %frequency, duration = %duration, sample_rate = %sample_rate))]
 pub fn generate_square_wave(frequency: f64, duration: f64, sample_rate: f64) -> Vec<f64> {
-    let num_samples = (duration * sample_rate) as usize;
-    debug!("Generating {} samples of square wave at {} Hz", num_samples, frequency);
-
-    let mut samples = Vec::with_capacity(num_samples);
-    let period_samples = (sample_rate / frequency) as usize;
-
-    for i in 0..num_samples {
-        let phase = (i % period_samples) as f64 / period_samples as f64;
-        let sample = if phase < 0.5 { 1.0 } else { -1.0 };
-        samples.push(sample);
-    }
-
-    debug!("Square wave generation complete: {} samples", samples.len());
-    samples
+    generate_waveform(Waveform::Square, frequency, duration, sample_rate)
 }
@@ -0,0 +1,121 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use crate::traits::{
+    Component,
+    Source,
+};
+
+pub struct StepParams {
+    pub at: f64,
+    pub level: f64,
+}
+
+impl Default for StepParams {
+    fn default() -> Self {
+        Self { at: 0.0, level: 1.0 }
+    }
+}
+
+impl StepParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "at" => result.at = kv[1].parse().map_err(|_| eyre!("Invalid at value"))?,
+                "level" => {
+                    result.level = kv[1].parse().map_err(|_| eyre!("Invalid level value"))?
+                }
+                _ => bail!("Unknown parameter: {}", kv[0]),
+            }
+        }
+        Ok(result)
+    }
+}
+
+pub struct StepSource {
+    pub at: f64,
+    pub level: f64,
+}
+
+impl StepSource {
+    #[instrument(level = "debug", fields(at = %at, level = %level))]
+    pub fn new(at: f64, level: f64) -> Self {
+        debug!("Creating step source at {} s to level {}", at, level);
+        Self { at, level }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "step" {
+            bail!("Not a step spec");
+        }
+        let params = StepParams::parse(&parts[1..])?;
+        debug!("Step source created at {} s from spec", params.at);
+        Ok(Self::new(params.at, params.level))
+    }
+}
+
+impl Source for StepSource {
+    #[instrument(skip(self), fields(at = %self.at, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        let index = (self.at * sample_rate).round().max(0.0) as usize;
+        debug!("Generating {} samples stepping at sample {}", num_samples, index);
+
+        (0..num_samples).map(|i| if i >= index { self.level } else { 0.0 }).collect()
+    }
+}
+
+impl Component for StepSource {
+    #[instrument(skip(self, buffer), fields(at = %self.at))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing step source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!("step:at={}:level={}", self.at, self.level)
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn switches_to_level_at_requested_time() {
+        let samples =
+            StepSource::from_spec("step:at=0.05:level=0.25").unwrap().generate(0.1, 1000.0);
+        assert_eq!(samples.len(), 100);
+        assert!(samples[..50].iter().all(|&s| s == 0.0));
+        assert!(samples[50..].iter().all(|&s| s == 0.25));
+    }
+}
@@ -0,0 +1,224 @@
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    instrument,
+};
+
+use super::oscillator::{
+    OscillatorParams,
+    Waveform,
+};
+use crate::traits::{
+    Component,
+    Source,
+};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SweepMode {
+    Linear,
+    Log,
+}
+
+impl SweepMode {
+    pub fn parse(name: &str) -> Result<Self> {
+        match name {
+            "linear" | "lin" => Ok(Self::Linear),
+            "log" | "exp" => Ok(Self::Log),
+            _ => bail!("Unknown sweep mode: {}", name),
+        }
+    }
+
+    pub fn name(&self) -> &'static str {
+        match self {
+            Self::Linear => "linear",
+            Self::Log => "log",
+        }
+    }
+}
+
+pub struct SweepParams {
+    pub from: f64,
+    pub to: f64,
+    pub mode: SweepMode,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for SweepParams {
+    fn default() -> Self {
+        Self {
+            from: 20.0,
+            to: 20000.0,
+            mode: SweepMode::Log,
+            oscillator: OscillatorParams::default(),
+        }
+    }
+}
+
+impl SweepParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "from" => result.from = kv[1].parse().map_err(|_| eyre!("Invalid from value"))?,
+                "to" => result.to = kv[1].parse().map_err(|_| eyre!("Invalid to value"))?,
+                "mode" => result.mode = SweepMode::parse(kv[1])?,
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        if result.mode == SweepMode::Log && (result.from <= 0.0 || result.to <= 0.0) {
+            bail!("Logarithmic sweeps require positive from and to frequencies");
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+pub struct SweepSource {
+    pub from: f64,
+    pub to: f64,
+    pub mode: SweepMode,
+    pub oscillator: OscillatorParams,
+}
+
+impl SweepSource {
+    #[instrument(level = "debug", fields(from = %from, to = %to, mode = ?mode))]
+    pub fn new(from: f64, to: f64, mode: SweepMode, oscillator: OscillatorParams) -> Self {
+        debug!("Creating {} sweep source from {} Hz to {} Hz", mode.name(), from, to);
+        Self { from, to, mode, oscillator }
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "sweep" {
+            bail!("Not a sweep spec");
+        }
+        let params = SweepParams::parse(&parts[1..])?;
+        debug!("Sweep source created from {} Hz to {} Hz from spec", params.from, params.to);
+        Ok(Self::new(params.from, params.to, params.mode, params.oscillator))
+    }
+
+    /// Frequency in Hz being produced at time `t` of a sweep lasting
+    /// `duration` seconds. Both are measured from the oscillator's `start`.
+    pub fn instantaneous_frequency(&self, t: f64, duration: f64) -> f64 {
+        let x = if duration > 0.0 { (t / duration).clamp(0.0, 1.0) } else { 0.0 };
+        match self.mode {
+            SweepMode::Linear => self.from + (self.to - self.from) * x,
+            SweepMode::Log => self.from * (self.to / self.from).powf(x),
+        }
+    }
+
+    /// Inverse of [`Self::instantaneous_frequency`]: the time at which
+    /// `frequency` is reached, if the sweep passes through it.
+    pub fn time_at_frequency(&self, frequency: f64, duration: f64) -> Option<f64> {
+        let (low, high) = (self.from.min(self.to), self.from.max(self.to));
+        if frequency < low || frequency > high || self.from == self.to {
+            return None;
+        }
+        let x = match self.mode {
+            SweepMode::Linear => (frequency - self.from) / (self.to - self.from),
+            SweepMode::Log => (frequency / self.from).ln() / (self.to / self.from).ln(),
+        };
+        Some(x * duration)
+    }
+}
+
+impl Source for SweepSource {
+    #[instrument(skip(self), fields(from = %self.from, to = %self.to, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!(
+            "Generating {} sweep: {} Hz to {} Hz over {} samples",
+            self.mode.name(),
+            self.from,
+            self.to,
+            num_samples
+        );
+
+        let sweep_duration = self.oscillator.active_duration(duration);
+        let mut oscillator = self.oscillator.oscillator(Waveform::Sine);
+        let samples = self.oscillator.render(duration, sample_rate, |i| {
+            let frequency = self.instantaneous_frequency(i as f64 / sample_rate, sweep_duration);
+            oscillator.next_sample(frequency / sample_rate)
+        });
+
+        debug!("Sweep generation complete: {} samples", samples.len());
+        samples
+    }
+}
+
+impl Component for SweepSource {
+    #[instrument(skip(self, buffer), fields(from = %self.from, to = %self.to))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing sweep source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "sweep:from={}:to={}:mode={}{}",
+            self.from,
+            self.to,
+            self.mode.name(),
+            self.oscillator
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn cycles(samples: &[f64]) -> usize {
+        samples.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count()
+    }
+
+    #[test]
+    fn linear_sweep_passes_through_midpoint_frequency() {
+        let sweep = SweepSource::from_spec("sweep:from=100:to=1100:mode=linear").unwrap();
+        assert_eq!(sweep.instantaneous_frequency(0.5, 1.0), 600.0);
+        let samples = sweep.generate(1.0, 48000.0);
+        // Integral of the frequency: 175 cycles in the first half, 425 in the second
+        let (first, second) = samples.split_at(24000);
+        assert!((174..=176).contains(&cycles(first)), "{} cycles", cycles(first));
+        assert!((424..=426).contains(&cycles(second)), "{} cycles", cycles(second));
+    }
+
+    #[test]
+    fn log_sweep_spends_equal_time_per_octave() {
+        let sweep = SweepSource::from_spec("sweep:from=100:to=10000:mode=log").unwrap();
+        assert!((sweep.instantaneous_frequency(0.5, 1.0) - 1000.0).abs() < 1e-9);
+        assert!((sweep.time_at_frequency(1000.0, 1.0).unwrap() - 0.5).abs() < 1e-9);
+        let samples = sweep.generate(1.0, 48000.0);
+        // 100 · (10 - 1) / ln 100 ≈ 195.4 cycles in the first half
+        let (first, _) = samples.split_at(24000);
+        assert!((194..=196).contains(&cycles(first)), "{} cycles", cycles(first));
+    }
+}
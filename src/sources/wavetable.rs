@@ -0,0 +1,336 @@
+use std::f64::consts::PI;
+
+use color_eyre::Result;
+use color_eyre::eyre::{
+    bail,
+    eyre,
+};
+use tracing::{
+    debug,
+    info,
+    instrument,
+    warn,
+};
+
+use super::oscillator::OscillatorParams;
+use crate::audio::read_wav;
+use crate::traits::{
+    Component,
+    Source,
+};
+
+/// Largest frame the mip-maps are built from. Building costs O(n²) per
+/// frame, and single-cycle tables are rarely longer than this.
+const MAX_FRAME_SIZE: usize = 4096;
+
+pub struct WavetableParams {
+    pub path: String,
+    pub freq: f64,
+    pub frame_size: Option<usize>,
+    pub position: f64,
+    pub oscillator: OscillatorParams,
+}
+
+impl Default for WavetableParams {
+    fn default() -> Self {
+        Self {
+            path: String::new(),
+            freq: 440.0,
+            frame_size: None,
+            position: 0.0,
+            oscillator: OscillatorParams::default(),
+        }
+    }
+}
+
+impl WavetableParams {
+    #[instrument]
+    pub fn parse(params: &[&str]) -> Result<Self> {
+        let mut result = Self::default();
+        for param in params {
+            let kv: Vec<&str> = param.split('=').collect();
+            if kv.len() != 2 {
+                bail!("Invalid parameter format: {}", param);
+            }
+            match kv[0] {
+                "path" => result.path = kv[1].to_string(),
+                "freq" => result.freq = kv[1].parse().map_err(|_| eyre!("Invalid freq value"))?,
+                "frame_size" => {
+                    result.frame_size =
+                        Some(kv[1].parse().map_err(|_| eyre!("Invalid frame_size value"))?)
+                }
+                "position" => {
+                    result.position = kv[1].parse().map_err(|_| eyre!("Invalid position value"))?
+                }
+                _ => {
+                    if !result.oscillator.parse_param(kv[0], kv[1])? {
+                        bail!("Unknown parameter: {}", kv[0]);
+                    }
+                }
+            }
+        }
+        if result.path.is_empty() {
+            bail!("wavetable requires a path: wavetable:path=table.wav:freq=440");
+        }
+        if !(0.0..=1.0).contains(&result.position) {
+            bail!("Wavetable position must be between 0 and 1");
+        }
+        result.oscillator.validate()?;
+        Ok(result)
+    }
+}
+
+/// One frame of the table, stored as band-limited copies. Level `k` keeps
+/// the harmonics below `(frame_size / 2) >> k`.
+struct MipMap {
+    levels: Vec<Vec<f64>>,
+}
+
+impl MipMap {
+    fn build(frame: &[f64]) -> Self {
+        let n = frame.len();
+        let max_harmonic = (n / 2).saturating_sub(1).max(1);
+        let cos_table: Vec<f64> = (0..n).map(|i| (2.0 * PI * i as f64 / n as f64).cos()).collect();
+        let sin_table: Vec<f64> = (0..n).map(|i| (2.0 * PI * i as f64 / n as f64).sin()).collect();
+
+        let dc = frame.iter().sum::<f64>() / n as f64;
+        let harmonics: Vec<(f64, f64)> = (1..=max_harmonic)
+            .map(|k| {
+                let (mut a, mut b) = (0.0, 0.0);
+                for (i, &x) in frame.iter().enumerate() {
+                    a += x * cos_table[(k * i) % n];
+                    b += x * sin_table[(k * i) % n];
+                }
+                (2.0 * a / n as f64, 2.0 * b / n as f64)
+            })
+            .collect();
+
+        let mut levels = Vec::new();
+        let mut limit = max_harmonic;
+        loop {
+            let table: Vec<f64> = (0..n)
+                .map(|i| {
+                    harmonics[..limit].iter().enumerate().fold(dc, |acc, (h, &(a, b))| {
+                        let index = ((h + 1) * i) % n;
+                        acc + a * cos_table[index] + b * sin_table[index]
+                    })
+                })
+                .collect();
+            levels.push(table);
+            if limit == 1 {
+                break;
+            }
+            limit /= 2;
+        }
+        Self { levels }
+    }
+
+    /// Picks the richest level whose harmonics all stay below Nyquist.
+    fn level_for(&self, frequency: f64, sample_rate: f64) -> &[f64] {
+        let allowed = (0.5 * sample_rate / frequency.abs().max(f64::EPSILON)).floor() as usize;
+        let n = self.levels[0].len();
+        let mut limit = (n / 2).saturating_sub(1).max(1);
+        for level in &self.levels {
+            if limit <= allowed {
+                return level;
+            }
+            limit /= 2;
+        }
+        &self.levels[self.levels.len() - 1]
+    }
+}
+
+fn read_table(table: &[f64], phase: f64) -> f64 {
+    let position = phase * table.len() as f64;
+    let index = position as usize % table.len();
+    let next = (index + 1) % table.len();
+    let frac = position - position.floor();
+    table[index] + (table[next] - table[index]) * frac
+}
+
+pub struct WavetableSource {
+    pub path: String,
+    pub frequency: f64,
+    pub frame_size: usize,
+    pub position: f64,
+    pub num_frames: usize,
+    pub oscillator: OscillatorParams,
+    lower: MipMap,
+    upper: MipMap,
+    blend: f64,
+}
+
+impl WavetableSource {
+    #[instrument(level = "debug", fields(path = %path, frequency = %frequency, position = %position))]
+    pub fn new(
+        path: &str,
+        frequency: f64,
+        frame_size: Option<usize>,
+        position: f64,
+        oscillator: OscillatorParams,
+    ) -> Result<Self> {
+        let data = read_wav(path).map_err(|e| eyre!("Failed to read {}: {}", path, e))?;
+        let table = data.channels.into_iter().next().unwrap_or_default();
+        if table.len() < 2 {
+            bail!("Wavetable {} is too short", path);
+        }
+
+        let frame_size = match frame_size {
+            Some(frame_size) => frame_size,
+            None if table.len() <= MAX_FRAME_SIZE => table.len(),
+            None => bail!(
+                "Wavetable {} has {} samples; set frame_size (at most {}) to split it into frames",
+                path,
+                table.len(),
+                MAX_FRAME_SIZE
+            ),
+        };
+        if frame_size < 2 || frame_size > table.len() || frame_size > MAX_FRAME_SIZE {
+            bail!(
+                "Invalid frame_size {} for a table of {} samples (expected 2 to {})",
+                frame_size,
+                table.len(),
+                MAX_FRAME_SIZE.min(table.len())
+            );
+        }
+        if table.len() % frame_size != 0 {
+            warn!(
+                "Wavetable {} ends with a partial frame of {} samples, which is ignored",
+                path,
+                table.len() % frame_size
+            );
+        }
+
+        // Only the two frames either side of `position` are ever played, so
+        // only those get band-limited copies.
+        let frames: Vec<&[f64]> = table.chunks_exact(frame_size).collect();
+        let scaled = position * (frames.len() - 1) as f64;
+        let first = scaled.floor() as usize;
+        let second = (first + 1).min(frames.len() - 1);
+        let lower = MipMap::build(frames[first]);
+        let upper = MipMap::build(frames[second]);
+
+        info!("Loaded wavetable {} with {} frame(s) of {} samples", path, frames.len(), frame_size);
+        Ok(Self {
+            path: path.to_string(),
+            frequency,
+            frame_size,
+            position,
+            num_frames: frames.len(),
+            oscillator,
+            lower,
+            upper,
+            blend: scaled - first as f64,
+        })
+    }
+
+    #[instrument(level = "debug")]
+    pub fn from_spec(spec: &str) -> Result<Self> {
+        let parts: Vec<&str> = spec.split(':').collect();
+        if parts[0] != "wavetable" {
+            bail!("Not a wavetable spec");
+        }
+        let params = WavetableParams::parse(&parts[1..])?;
+        debug!("Wavetable source created for {} at {} Hz from spec", params.path, params.freq);
+        Self::new(&params.path, params.freq, params.frame_size, params.position, params.oscillator)
+    }
+}
+
+impl Source for WavetableSource {
+    #[instrument(skip(self), fields(path = %self.path, frequency = %self.frequency, duration = %duration, sample_rate = %sample_rate))]
+    fn generate(&self, duration: f64, sample_rate: f64) -> Vec<f64> {
+        let num_samples = (duration * sample_rate) as usize;
+        debug!(
+            "Generating {} wavetable samples at {} Hz (position {})",
+            num_samples, self.frequency, self.position
+        );
+
+        let table_a = self.lower.level_for(self.frequency, sample_rate);
+        let table_b = self.upper.level_for(self.frequency, sample_rate);
+
+        let increment = self.frequency / sample_rate;
+        let mut phase = (self.oscillator.phase / 360.0).rem_euclid(1.0);
+        let samples = self.oscillator.render(duration, sample_rate, |_| {
+            let a = read_table(table_a, phase);
+            let b = read_table(table_b, phase);
+            phase = (phase + increment).rem_euclid(1.0);
+            a + (b - a) * self.blend
+        });
+
+        debug!("Wavetable generation complete: {} samples", samples.len());
+        samples
+    }
+}
+
+impl Component for WavetableSource {
+    #[instrument(skip(self, buffer), fields(path = %self.path, frequency = %self.frequency))]
+    fn process(&mut self, buffer: &mut Vec<f64>, duration: f64, sample_rate: f64) -> Result<()> {
+        debug!("Processing wavetable source");
+        *buffer = self.generate(duration, sample_rate);
+        debug!("Generated {} samples", buffer.len());
+        Ok(())
+    }
+
+    fn is_source(&self) -> bool {
+        true
+    }
+
+    fn get_samples(&self, duration: f64, sample_rate: f64) -> Option<Vec<f64>> {
+        Some(self.generate(duration, sample_rate))
+    }
+
+    fn name(&self) -> String {
+        format!(
+            "wavetable:path={}:freq={}:frame_size={}:position={}{}",
+            self.path, self.frequency, self.frame_size, self.position, self.oscillator
+        )
+    }
+
+    fn component_type(&self) -> &'static str {
+        "Source"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::audio::write_wav;
+
+    /// Writes `num_frames` cycles of a sine, `frame_size` samples each plus
+    /// `extra` trailing samples, and loads them as a table.
+    fn load(
+        name: &str,
+        frame_size: usize,
+        num_frames: usize,
+        extra: usize,
+        spec: &str,
+    ) -> Result<WavetableSource> {
+        let table: Vec<f64> = (0..frame_size * num_frames + extra)
+            .map(|i| 0.5 * (2.0 * PI * i as f64 / frame_size as f64).sin())
+            .collect();
+        let path = std::env::temp_dir().join(format!("noise-{}-{}.wav", name, std::process::id()));
+        let path = path.to_str().unwrap();
+        write_wav(path, &table, 48000.0).unwrap();
+        let source = WavetableSource::from_spec(&format!("wavetable:path={}{}", path, spec));
+        std::fs::remove_file(path).unwrap();
+        source
+    }
+
+    #[test]
+    fn single_cycle_table_plays_at_frequency() {
+        let source = load("single", 2048, 1, 0, ":freq=1000").unwrap();
+        assert_eq!((source.frame_size, source.num_frames), (2048, 1));
+        let samples = source.generate(1.0, 48000.0);
+        let rising = samples.windows(2).filter(|w| w[0] < 0.0 && w[1] >= 0.0).count();
+        assert!((999..=1000).contains(&rising), "{} cycles", rising);
+    }
+
+    #[test]
+    fn long_tables_need_a_bounded_frame_size() {
+        assert!(load("unframed", 2048, 4, 0, "").is_err());
+        assert!(load("oversized", 8192, 1, 0, ":frame_size=8192").is_err());
+
+        let source = load("framed", 1024, 4, 100, ":frame_size=1024").unwrap();
+        assert_eq!(source.num_frames, 4);
+    }
+}
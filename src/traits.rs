@@ -8,6 +8,16 @@ pub trait Component {
     fn get_samples(&self, _duration: f64, _sample_rate: f64) -> Option<Vec<f64>> {
         None
     }
+    /// Sample rate of the buffer after this component, given the rate it
+    /// receives. Only rate converters change it.
+    fn output_sample_rate(&self, sample_rate: f64) -> f64 {
+        sample_rate
+    }
+    /// Gain change in dB applied to each sample on the last run, for
+    /// components that reduce gain dynamically.
+    fn gain_reduction(&self) -> Option<&[f64]> {
+        None
+    }
 
     fn name(&self) -> String;
 